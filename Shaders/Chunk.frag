@@ -19,15 +19,46 @@
 out vec4 outColour;
 in  vec2 passTextureCoord;
 in float passCardinalLight;
+in float passTextureLayer;
+in  vec2 passNextTextureCoord;
+in float passNextTextureLayer;
+in float passFrameBlend;
+in  vec3 passTint;
+in float passDistance;
 
+#ifdef TEXTURE_ARRAYS
+uniform sampler2DArray texSampler;
+#else
 uniform sampler2D texSampler;
+#endif
+uniform float daylight;
+
+#include "Fog.glsl"
 
 vec4 color;
 
+vec4 sampleBlock(vec2 textureCoord, float textureLayer) {
+#ifdef TEXTURE_ARRAYS
+    return texture(texSampler, vec3(textureCoord, textureLayer));
+#else
+    return texture(texSampler, textureCoord);
+#endif
+}
+
 void main() {
-    color = texture(texSampler, passTextureCoord);
+    // Animated textures fade from one frame into the next, sampled outside of any branch
+    // so that both keep their mipmap level
+    color = mix(
+        sampleBlock(passTextureCoord, passTextureLayer),
+        sampleBlock(passNextTextureCoord, passNextTextureLayer),
+        passFrameBlend
+    );
+    color.rgb *= passTint;
 
     outColour = color * passCardinalLight;
+    outColour.rgb *= daylight;
+
+    outColour.rgb = applyFog(outColour.rgb, passDistance);
     if (outColour.a == 0)
         discard;
 }
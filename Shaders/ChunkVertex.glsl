@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// Vertex attributes of chunk meshes, packed the way ChunkMesh packs them,
+// and what every chunk vertex shader passes on to Chunk.frag.
+//
+// inPacked:   x (5 bits), y (9), z (5) relative to the mesh origin, corner (2),
+//             light level (2) and texture index (9), from the lowest bit up
+// inMaterial: tint, 8 bits per channel
+
+layout(location = 0) in uint inPacked;
+layout(location = 1) in uint inMaterial;
+
+out vec2 passTextureCoord;
+out float passCardinalLight;
+out float passTextureLayer;
+// Frame an animated texture blends into, and how far
+out vec2 passNextTextureCoord;
+out float passNextTextureLayer;
+out float passFrameBlend;
+out vec3 passTint;
+out float passDistance;
+
+uniform mat4 projViewMatrix;
+uniform vec3 cameraPosition;
+
+// Origin of the mesh owning each page of ORIGIN_PAGE_SIZE vertices
+uniform samplerBuffer sectionOrigins;
+#ifndef TEXTURE_ARRAYS
+// Lowest and highest texture coordinates of every atlas region
+uniform samplerBuffer textureRegions;
+#endif
+// Frame sequence, frame count, frame time and blending of every texture,
+// followed by the frames of each sequence (see AnimationTable)
+uniform samplerBuffer textureAnimations;
+uniform float animationTime;
+
+vec3 chunkVertexPosition() {
+    vec3 position = vec3(inPacked & 31u, (inPacked >> 5) & 511u, (inPacked >> 14) & 31u);
+    return texelFetch(sectionOrigins, gl_VertexID / ORIGIN_PAGE_SIZE).xyz + position;
+}
+
+// Texture indices of the frame an animated texture is at and of the one after it
+uvec2 animationFrames(uint textureIndex) {
+    vec4 animation = texelFetch(textureAnimations, int(textureIndex));
+    if (animation.y < 2.0) {
+        passFrameBlend = 0.0;
+        return uvec2(textureIndex);
+    }
+
+    float position = animationTime / animation.z;
+    int frame = int(mod(floor(position), animation.y));
+    int next = int(mod(float(frame + 1), animation.y));
+    passFrameBlend = animation.w > 0.5 ? fract(position) : 0.0;
+    return uvec2(
+        texelFetch(textureAnimations, int(animation.x) + frame).x,
+        texelFetch(textureAnimations, int(animation.x) + next).x
+    );
+}
+
+void passChunkAttributes(vec3 worldPosition) {
+    uint corner = (inPacked >> 19) & 3u;
+    uvec2 frames = animationFrames(inPacked >> 23);
+
+    // Corners go around the face starting from the highest texture coordinates
+    vec2 cornerCoord = vec2(corner == 0u || corner == 3u ? 1.0 : 0.0, corner < 2u ? 1.0 : 0.0);
+#ifdef TEXTURE_ARRAYS
+    passTextureCoord = cornerCoord;
+    passNextTextureCoord = cornerCoord;
+    passTextureLayer = float(frames.x);
+    passNextTextureLayer = float(frames.y);
+#else
+    vec4 region = texelFetch(textureRegions, int(frames.x));
+    vec4 nextRegion = texelFetch(textureRegions, int(frames.y));
+    passTextureCoord = mix(region.xy, region.zw, cornerCoord);
+    passNextTextureCoord = mix(nextRegion.xy, nextRegion.zw, cornerCoord);
+    passTextureLayer = 0.0;
+    passNextTextureLayer = 0.0;
+#endif
+
+    passCardinalLight = 1.0 - 0.2 * float((inPacked >> 21) & 3u);
+    passTint = vec3(inMaterial & 255u, (inMaterial >> 8) & 255u, (inMaterial >> 16) & 255u) / 255.0;
+    passDistance = distance(worldPosition, cameraPosition);
+}
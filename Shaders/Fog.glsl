@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+// Fades colours into the fog, which starts at fogStart and is opaque from fogEnd blocks away.
+
+uniform vec3 fogColour;
+uniform float fogStart;
+uniform float fogEnd;
+
+vec3 applyFog(vec3 colour, float distance) {
+    float fog = clamp((distance - fogStart) / (fogEnd - fogStart), 0.0, 1.0);
+    return mix(colour, fogColour, fog);
+}
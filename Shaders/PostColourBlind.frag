@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#version 330
+
+out vec4 outColour;
+in  vec2 passTextureCoord;
+
+uniform sampler2D screenTexture;
+// 1 = protanopia, 2 = deuteranopia, 3 = tritanopia
+uniform int mode;
+
+// Rows of the colour transforms simulating each deficiency
+const vec3 PROTANOPIA[3] = vec3[](
+    vec3(0.567, 0.433, 0.0),
+    vec3(0.558, 0.442, 0.0),
+    vec3(0.0, 0.242, 0.758)
+);
+const vec3 DEUTERANOPIA[3] = vec3[](
+    vec3(0.625, 0.375, 0.0),
+    vec3(0.7, 0.3, 0.0),
+    vec3(0.0, 0.3, 0.7)
+);
+const vec3 TRITANOPIA[3] = vec3[](
+    vec3(0.95, 0.05, 0.0),
+    vec3(0.0, 0.433, 0.567),
+    vec3(0.0, 0.475, 0.525)
+);
+
+vec3 transform(vec3 colour, vec3 rows[3]) {
+    return vec3(dot(rows[0], colour), dot(rows[1], colour), dot(rows[2], colour));
+}
+
+void main() {
+    vec3 colour = texture(screenTexture, passTextureCoord).rgb;
+
+    if (mode == 1) {
+        colour = transform(colour, PROTANOPIA);
+    } else if (mode == 2) {
+        colour = transform(colour, DEUTERANOPIA);
+    } else if (mode == 3) {
+        colour = transform(colour, TRITANOPIA);
+    }
+
+    outColour = vec4(colour, 1.0);
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#version 330
+
+out vec4 outColour;
+in  vec2 passTextureCoord;
+
+uniform sampler2D screenTexture;
+uniform vec2 resolution;
+
+const float FXAA_SPAN_MAX = 8.0;
+const float FXAA_REDUCE_MUL = 1.0 / 8.0;
+const float FXAA_REDUCE_MIN = 1.0 / 128.0;
+const vec3 LUMA = vec3(0.299, 0.587, 0.114);
+
+vec3 sampleAt(vec2 offset) {
+    return texture(screenTexture, passTextureCoord + offset).rgb;
+}
+
+void main() {
+    vec2 texel = 1.0 / resolution;
+
+    vec3 rgbNW = sampleAt(vec2(-1.0, -1.0) * texel);
+    vec3 rgbNE = sampleAt(vec2(1.0, -1.0) * texel);
+    vec3 rgbSW = sampleAt(vec2(-1.0, 1.0) * texel);
+    vec3 rgbSE = sampleAt(vec2(1.0, 1.0) * texel);
+    vec3 rgbM  = sampleAt(vec2(0.0));
+
+    float lumaNW = dot(rgbNW, LUMA);
+    float lumaNE = dot(rgbNE, LUMA);
+    float lumaSW = dot(rgbSW, LUMA);
+    float lumaSE = dot(rgbSE, LUMA);
+    float lumaM  = dot(rgbM, LUMA);
+
+    float lumaMin = min(lumaM, min(min(lumaNW, lumaNE), min(lumaSW, lumaSE)));
+    float lumaMax = max(lumaM, max(max(lumaNW, lumaNE), max(lumaSW, lumaSE)));
+
+    // Blur along the edge, perpendicular to the luma gradient
+    vec2 direction = vec2(
+        -((lumaNW + lumaNE) - (lumaSW + lumaSE)),
+        (lumaNW + lumaSW) - (lumaNE + lumaSE)
+    );
+    float directionReduce = max(
+        (lumaNW + lumaNE + lumaSW + lumaSE) * (0.25 * FXAA_REDUCE_MUL),
+        FXAA_REDUCE_MIN
+    );
+    float inverseDirectionMin = 1.0 / (min(abs(direction.x), abs(direction.y)) + directionReduce);
+    direction = clamp(direction * inverseDirectionMin, vec2(-FXAA_SPAN_MAX), vec2(FXAA_SPAN_MAX)) * texel;
+
+    vec3 rgbA = 0.5 * (
+        sampleAt(direction * (1.0 / 3.0 - 0.5)) +
+        sampleAt(direction * (2.0 / 3.0 - 0.5))
+    );
+    vec3 rgbB = rgbA * 0.5 + 0.25 * (
+        sampleAt(direction * -0.5) +
+        sampleAt(direction * 0.5)
+    );
+    float lumaB = dot(rgbB, LUMA);
+
+    outColour = vec4((lumaB < lumaMin || lumaB > lumaMax) ? rgbA : rgbB, 1.0);
+}
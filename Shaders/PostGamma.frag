@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#version 330
+
+out vec4 outColour;
+in  vec2 passTextureCoord;
+
+uniform sampler2D screenTexture;
+uniform float gamma;
+uniform float brightness;
+
+void main() {
+    vec3 colour = texture(screenTexture, passTextureCoord).rgb * brightness;
+    colour = clamp(colour, 0.0, 1.0);
+
+    outColour = vec4(pow(colour, vec3(1.0 / gamma)), 1.0);
+}
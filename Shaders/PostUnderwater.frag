@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#version 330
+
+out vec4 outColour;
+in  vec2 passTextureCoord;
+
+uniform sampler2D screenTexture;
+uniform float time;
+
+const vec3 TINT = vec3(0.55, 0.75, 1.0);
+const float WOBBLE_STRENGTH = 0.003;
+
+void main() {
+    vec2 offset = vec2(
+        sin(passTextureCoord.y * 25.0 + time * 2.0),
+        cos(passTextureCoord.x * 25.0 + time * 1.7)
+    ) * WOBBLE_STRENGTH;
+    vec3 colour = texture(screenTexture, passTextureCoord + offset).rgb;
+
+    outColour = vec4(colour * TINT, 1.0);
+}
@@ -21,6 +21,21 @@ in  vec3 passTextureCoord;
 
 uniform samplerCube texSampler;
 
+uniform vec3 sunDirection;
+uniform vec3 zenithColour;
+uniform vec3 horizonColour;
+uniform vec3 sunsetColour;
+uniform float daylight;
+uniform float starVisibility;
+uniform float celestialAngle;
+
+uniform vec3 fogColour;
+uniform float fogHeight;
+
+const float SUN_SIZE = 0.9995;
+const float MOON_SIZE = 0.9997;
+const float CUBE_MAP_BLEND = 0.35;
+
 vec3 brightnessContrast(vec3 value, float brightness, float contrast) {
     return (value - 0.5) * contrast + 0.5 + (brightness-1);
 }
@@ -35,12 +50,51 @@ vec3 gamma(vec3 value, float param) {
     );
 }
 
+float hash(vec3 p) {
+    p = fract(p * 0.3183099 + 0.1);
+    p *= 17.0;
+    return fract(p.x * p.y * p.z * (p.x + p.y + p.z));
+}
+
+// Stars are fixed to the sky, so they turn together with the sun and moon.
+float stars(vec3 direction) {
+    float c = cos(celestialAngle);
+    float s = sin(celestialAngle);
+    vec3 skyDirection = vec3(
+        c * direction.x + s * direction.y,
+        -s * direction.x + c * direction.y,
+        direction.z
+    );
+    vec3 cell = floor(skyDirection * 250.0);
+    return step(0.998, hash(cell)) * hash(cell + 7.0);
+}
 
 void main() {
+    vec3 direction = normalize(passTextureCoord);
+    float height = max(direction.y, 0.0);
+
+    // Gradient from the horizon up to the zenith, with a glow around the sun near the horizon
+    vec3 sky = mix(horizonColour, zenithColour, pow(height, 0.5));
+    float sunAmount = max(dot(direction, sunDirection), 0.0);
+    sky += sunsetColour * pow(sunAmount, 8.0) * (1.0 - height);
+
+    // The original cube map still provides the clouds during the day
     color = texture(texSampler, passTextureCoord);
     color = vec4(brightnessContrast(color.xyz, 1.15f, 1.15f), color.w);
     color = vec4(gamma(color.xyz, 4.8f),color.w);
+    sky = mix(sky, color.rgb, CUBE_MAP_BLEND * daylight);
+
+    sky += vec3(stars(direction) * starVisibility) * step(0.0, direction.y);
+
+    float sun = smoothstep(SUN_SIZE, SUN_SIZE + 0.0002, dot(direction, sunDirection));
+    sky = mix(sky, vec3(1.0, 0.95, 0.8), sun);
+
+    float moon = smoothstep(MOON_SIZE, MOON_SIZE + 0.0001, dot(direction, -sunDirection));
+    sky = mix(sky, vec3(0.85, 0.87, 0.95), moon * (1.0 - daylight * 0.7));
+
+    // Fade into the fog around the horizon, so distant terrain blends into the sky
+    float fog = 1.0 - smoothstep(0.0, 1.0, clamp(direction.y / fogHeight, 0.0, 1.0));
+    sky = mix(sky, fogColour, fog);
 
-    outColour = color;
-    if (outColour.a == 0) discard;
+    outColour = vec4(sky, 1.0);
 }
@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#version 330
+
+out vec4 outColour;
+in  vec2 passTextureCoord;
+
+uniform sampler2D screenTexture;
+
+// Shows a texture as-is on a dark backdrop, images are stored top row first
+void main() {
+    vec4 colour = texture(screenTexture, vec2(passTextureCoord.x, 1.0 - passTextureCoord.y));
+    outColour = vec4(mix(vec3(0.1), colour.rgb, colour.a), 0.9);
+}
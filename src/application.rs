@@ -23,6 +23,7 @@ use crate::camera::Camera;
 use crate::config::Config;
 use crate::context::Context;
 use crate::renderer::render_master::RenderMaster;
+use crate::shaders::shader_loader;
 use crate::states::play_state::StatePlay;
 use crate::states::state_base::StateBase;
 use crate::util::unsafe_cell_wrapper::UnsafeCellWrapper;
@@ -38,22 +39,28 @@ pub struct Application {
     camera: Arc<UnsafeCellWrapper<Camera>>,
     #[allow(dead_code)]
     config: Config,
-    is_pop_state: bool
+    is_pop_state: bool,
+    pending_state: Option<Box<dyn StateBase>>
 }
 
 impl Application {
     pub fn new(config: Config) -> Rc<UnsafeCell<Self>> {
+        let context = Context::new(config.clone());
+        BlockDatabase::init(&config);
+        // Must happen before any shader is compiled
+        shader_loader::set_defines(&config);
+
         let result = Self {
             states: Vec::new(),
-            context: Context::new(config),
-            master_renderer: RenderMaster::default(),
-            camera: Arc::new(UnsafeCellWrapper::new(Camera::new(config))),
-            config,
-            is_pop_state: false
+            context,
+            master_renderer: RenderMaster::new(&config),
+            camera: Arc::new(UnsafeCellWrapper::new(Camera::new(config.clone()))),
+            config: config.clone(),
+            is_pop_state: false,
+            pending_state: None
         };
         let result = Rc::new(UnsafeCell::new(result));
 
-        BlockDatabase::get();
         unsafe {
             (*result.get()).push_state(StatePlay::new_boxed(Rc::clone(&result), config));
         }
@@ -89,6 +96,9 @@ impl Application {
                 self.is_pop_state = false;
                 self.states.pop();
             }
+            if let Some(state) = self.pending_state.take() {
+                self.push_state(state);
+            }
 
             m = dt.restart();
 
@@ -105,6 +115,12 @@ impl Application {
         s.on_open();
     }
 
+    /// @brief Pushes the state at the end of the current frame, so that the running state
+    /// can open another one.
+    pub fn queue_state(&mut self, state: Box<dyn StateBase>) {
+        self.pending_state = Some(state);
+    }
+
     /// @brief Tell the program stack to pop off the state.
     pub fn pop_state(&mut self) {
         self.is_pop_state = true;
@@ -35,7 +35,6 @@ pub struct Camera {
     view_matrix: glm::TMat4<f32>,
     proj_view_matrix: glm::TMat4<f32>,
 
-    #[allow(dead_code)]
     config: Config
 }
 
@@ -72,6 +71,18 @@ impl Camera {
         }
     }
 
+    /// @brief Camera fixed at the given position and rotation, not following any entity.
+    /// @param config
+    /// @param position
+    /// @param rotation
+    pub fn new_fixed(config: Config, position: &glm::TVec3<f32>, rotation: &glm::TVec3<f32>) -> Self {
+        let mut result = Self::new(config);
+        result.base.position = *position;
+        result.base.rotation = *rotation;
+        result.update_matrices();
+        result
+    }
+
     pub fn update(&mut self) {
         let wrapped_obj = &mut self.base;
         let p_entity = unsafe { &***self.p_entity.as_ref().unwrap() };
@@ -80,11 +91,15 @@ impl Camera {
         );
         wrapped_obj.rotation = p_entity.rotation;
 
+        self.update_matrices();
+
+        // println!("Camera frustum: {:#?}", self.frustum);
+    }
+
+    fn update_matrices(&mut self) {
         self.view_matrix = matrix::make_view_matrix(self);
         self.proj_view_matrix = self.projection_matrix * self.view_matrix;
         self.frustum.update(&self.proj_view_matrix);
-
-        // println!("Camera frustum: {:#?}", self.frustum);
     }
 
     pub fn hook_entity(&mut self, entity: *const Entity) {
@@ -106,6 +121,10 @@ impl Camera {
     pub fn get_frustum(&self) -> &ViewFrustum {
         &self.frustum
     }
+
+    pub fn get_config(&self) -> &Config {
+        &self.config
+    }
 }
 
 impl Deref for Camera {
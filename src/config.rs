@@ -14,14 +14,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::texture::texture_pack::DEFAULT_TEXTURE_PACK;
+
 /// @brief Default configuration for program.
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct Config {
     pub window_x: i32,
     pub window_y: i32,
     pub is_fullscreen: bool,
     pub render_distance: i32, // Set initial RD low to prevent long load times
-    pub fov: i32
+    pub lod_distance: i32, // Columns up to here are drawn with coarse meshes past the render distance, 0 = off
+    pub upload_budget: i32, // Kilobytes of chunk meshes uploaded to the GPU per frame, 0 = no limit
+    pub upload_time: f32, // Milliseconds spent uploading chunk meshes per frame, 0 = no limit
+    pub fov: i32,
+    pub terrain_generator: String, // "classic", "density" or "flat"
+    pub world_time: Option<u64>, // Overrides the saved time of day when set
+    pub texture_pack: String, // Directory name in Res/TexturePacks
+    pub texture_arrays: bool, // Block textures as a texture array instead of an atlas
+    pub anisotropy: f32, // Maximum anisotropic filtering level of texture arrays, 1 = off
+    pub hot_reload: bool, // Reload shaders, blocks and textures as soon as their files change
+
+    // Post-processing
+    pub gamma_correction: bool,
+    pub gamma: f32,
+    pub brightness: f32,
+    pub underwater_effect: bool,
+    pub vignette: bool,
+    pub fxaa: bool,
+    pub colour_blind_mode: i32 // 0 = off, 1 = protanopia, 2 = deuteranopia, 3 = tritanopia
 }
 
 impl Default for Config {
@@ -31,7 +51,23 @@ impl Default for Config {
             window_y: 720,
             is_fullscreen: false,
             render_distance: 8,
-            fov: 90
+            lod_distance: 0,
+            upload_budget: 1024,
+            upload_time: 4.0,
+            fov: 90,
+            terrain_generator: "classic".to_string(),
+            world_time: None,
+            texture_pack: DEFAULT_TEXTURE_PACK.to_string(),
+            texture_arrays: false,
+            anisotropy: 16.0,
+            hot_reload: false,
+            gamma_correction: true,
+            gamma: 1.0,
+            brightness: 1.0,
+            underwater_effect: true,
+            vignette: true,
+            fxaa: true,
+            colour_blind_mode: 0
         }
     }
 }
\ No newline at end of file
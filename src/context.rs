@@ -51,37 +51,48 @@ impl Context {
             }
         };
 
-        // Load OpenGL library.
-        gl_loader::init_gl();
-        // Load all the OpenGL function pointer using the `gl` crate.
-        gl::load_with(|symbol| gl_loader::get_proc_address(symbol) as *const _);
-        // Unload the OpenGL library.
-        gl_loader::end_gl();
-        
-        unsafe {
-            gl::Enable(gl::DEBUG_OUTPUT);
-            // makes sure errors are displayed synchronously
-            gl::Enable(gl::DEBUG_OUTPUT_SYNCHRONOUS);
-            gl::DebugMessageCallback(Some(gl_debug_output), ptr::null());
-            gl::DebugMessageControl(
-                gl::DONT_CARE,
-                gl::DONT_CARE,
-                gl::DONT_CARE,
-                0,
-                ptr::null(),
-                gl::TRUE
-            );
-            
-            gl::Viewport(0, 0, result.window.size().x as _, result.window.size().y as _);
-            
-            gl::CullFace(gl::BACK);
-            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
-        }
+        load_gl();
+        init_gl_state(result.window.size().x, result.window.size().y);
 
         result
     }
 }
 
+/// @brief Loads the OpenGL function pointers for the context active on this thread.
+pub fn load_gl() {
+    // Load OpenGL library.
+    gl_loader::init_gl();
+    // Load all the OpenGL function pointer using the `gl` crate.
+    gl::load_with(|symbol| gl_loader::get_proc_address(symbol) as *const _);
+    // Unload the OpenGL library.
+    gl_loader::end_gl();
+}
+
+/// @brief Sets up the GL state every renderer expects, for a surface of the given size.
+/// @param width
+/// @param height
+pub fn init_gl_state(width: u32, height: u32) {
+    unsafe {
+        gl::Enable(gl::DEBUG_OUTPUT);
+        // makes sure errors are displayed synchronously
+        gl::Enable(gl::DEBUG_OUTPUT_SYNCHRONOUS);
+        gl::DebugMessageCallback(Some(gl_debug_output), ptr::null());
+        gl::DebugMessageControl(
+            gl::DONT_CARE,
+            gl::DONT_CARE,
+            gl::DONT_CARE,
+            0,
+            ptr::null(),
+            gl::TRUE
+        );
+
+        gl::Viewport(0, 0, width as _, height as _);
+
+        gl::CullFace(gl::BACK);
+        gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+    }
+}
+
 extern "system" fn gl_debug_output(
     source: GLenum,
     gltype: GLenum,
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ptr;
+use gl::types::GLuint;
+
+/// @brief Off-screen render target with a floating point colour texture and a depth buffer.
+pub struct Framebuffer {
+    fbo: GLuint,
+    colour_texture: GLuint,
+    depth_buffer: GLuint,
+
+    width: u32,
+    height: u32
+}
+
+impl Framebuffer {
+    pub fn new(width: u32, height: u32) -> Self {
+        let mut result = Self {
+            fbo: 0,
+            colour_texture: 0,
+            depth_buffer: 0,
+            width,
+            height
+        };
+
+        unsafe {
+            gl::GenFramebuffers(1, &mut result.fbo);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, result.fbo);
+
+            // Colour is kept as half floats so values above 1 survive until tone mapping
+            gl::GenTextures(1, &mut result.colour_texture);
+            gl::BindTexture(gl::TEXTURE_2D, result.colour_texture);
+            gl::TexImage2D(
+                gl::TEXTURE_2D,
+                0,
+                gl::RGBA16F as _,
+                width as _,
+                height as _,
+                0,
+                gl::RGBA,
+                gl::FLOAT,
+                ptr::null()
+            );
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as _);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as _);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as _);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as _);
+            gl::FramebufferTexture2D(
+                gl::FRAMEBUFFER,
+                gl::COLOR_ATTACHMENT0,
+                gl::TEXTURE_2D,
+                result.colour_texture,
+                0
+            );
+
+            gl::GenRenderbuffers(1, &mut result.depth_buffer);
+            gl::BindRenderbuffer(gl::RENDERBUFFER, result.depth_buffer);
+            gl::RenderbufferStorage(gl::RENDERBUFFER, gl::DEPTH24_STENCIL8, width as _, height as _);
+            gl::FramebufferRenderbuffer(
+                gl::FRAMEBUFFER,
+                gl::DEPTH_STENCIL_ATTACHMENT,
+                gl::RENDERBUFFER,
+                result.depth_buffer
+            );
+
+            if gl::CheckFramebufferStatus(gl::FRAMEBUFFER) != gl::FRAMEBUFFER_COMPLETE {
+                log::error!("Framebuffer {}x{} is incomplete.", width, height);
+            }
+
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+
+        result
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.fbo);
+        }
+    }
+
+    /// @brief Goes back to drawing on the window.
+    pub fn bind_default() {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+    }
+
+    pub fn bind_colour_texture(&self) {
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, self.colour_texture);
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteRenderbuffers(1, &self.depth_buffer);
+            gl::DeleteTextures(1, &self.colour_texture);
+            gl::DeleteFramebuffers(1, &self.fbo);
+        }
+    }
+}
@@ -14,8 +14,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ffi::c_void;
 use std::ptr;
-use gl::types::{GLsizei, GLuint};
+use gl::types::{GLint, GLsizei, GLuint};
 
 pub fn draw_elements(indices_count: GLsizei) {
     unsafe {
@@ -23,8 +24,39 @@ pub fn draw_elements(indices_count: GLsizei) {
     }
 }
 
+/// @brief Draws several ranges of the bound element buffer with a single call.
+/// @param counts Index count of every range.
+/// @param offsets Byte offset of every range in the element buffer.
+/// @param base_vertices Added to the indices of every range.
+pub fn multi_draw_elements(counts: &[GLsizei], offsets: &[*const c_void], base_vertices: &[GLint]) {
+    unsafe {
+        gl::MultiDrawElementsBaseVertex(
+            gl::TRIANGLES,
+            counts.as_ptr(),
+            gl::UNSIGNED_INT,
+            offsets.as_ptr(),
+            counts.len() as _,
+            base_vertices.as_ptr() as _
+        );
+    }
+}
+
 pub fn bind_vao(vao: GLuint) {
     unsafe {
         gl::BindVertexArray(vao);
     }
-}
\ No newline at end of file
+}
+
+pub fn draw_line_elements(indices_count: GLsizei) {
+    unsafe {
+        gl::DrawElements(gl::LINES, indices_count, gl::UNSIGNED_INT, ptr::null());
+    }
+}
+
+/// @brief Draws a single triangle covering the whole screen, with the positions generated
+/// in the vertex shader. A (possibly empty) VAO still has to be bound.
+pub fn draw_fullscreen_triangle() {
+    unsafe {
+        gl::DrawArrays(gl::TRIANGLES, 0, 3);
+    }
+}
@@ -14,4 +14,5 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub mod gl_functions;
\ No newline at end of file
+pub mod gl_functions;
+pub mod framebuffer;
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use std::sync::Arc;
+use sfml::window::Context;
+use crate::camera::Camera;
+use crate::config::Config;
+use crate::context;
+use crate::player::player::Player;
+use crate::renderer::render_master::RenderMaster;
+use crate::shaders::shader_loader;
+use crate::util::random::RandomSingleton;
+use crate::util::unsafe_cell_wrapper::UnsafeCellWrapper;
+use crate::world::block::block_database::BlockDatabase;
+use crate::world::world::World;
+
+/// Time of day used when a capture does not ask for one, so that images are comparable.
+const DEFAULT_CAPTURE_TIME: u64 = 6000;
+
+/// @brief A single view of the world to render into an image without opening a window,
+/// e.g. for visual regression tests under a software rasterizer.
+pub struct CaptureRequest {
+    pub position: glm::TVec3<f32>,
+    pub rotation: glm::TVec3<f32>,
+    pub file_path: String,
+    pub seed: Option<u64>,
+    pub time: u64
+}
+
+impl CaptureRequest {
+    /// @brief Reads `--capture x y z pitch yaw output.png [seed] [time]` from the command line.
+    /// @param args Program arguments, without the executable name.
+    /// @return None when the arguments do not ask for a capture.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        if args.first().map(String::as_str) != Some("--capture") {
+            return None;
+        }
+        if args.len() < 7 {
+            panic!("Usage: --capture x y z pitch yaw output.png [seed] [time]");
+        }
+
+        let number = |i: usize| -> f32 {
+            args[i].parse().unwrap_or_else(|_| panic!("Invalid capture argument: {}", args[i]))
+        };
+
+        Some(Self {
+            position: glm::vec3(number(1), number(2), number(3)),
+            rotation: glm::vec3(number(4), number(5), 0.),
+            file_path: args[6].clone(),
+            seed: args.get(7).map(|seed| seed.parse().expect("Invalid capture seed")),
+            time: args.get(8).map_or(DEFAULT_CAPTURE_TIME, |time| time.parse().expect("Invalid capture time"))
+        })
+    }
+}
+
+/// @brief Renders the requested view with an off-screen GL context and saves it.
+/// The image has the size of the configured window.
+/// @param config
+/// @param request
+/// @return Whether the image was written.
+pub fn capture(config: Config, request: &CaptureRequest) -> bool {
+    let mut gl_context = Context::new();
+    gl_context.set_active(true);
+    context::load_gl();
+    context::init_gl_state(config.window_x as _, config.window_y as _);
+
+    // Must happen before the terrain generator draws its seed
+    if let Some(seed) = request.seed {
+        RandomSingleton::get().set_seed(seed);
+    }
+
+    BlockDatabase::init(&config);
+    shader_loader::set_defines(&config);
+    let mut renderer = RenderMaster::new(&config);
+    let camera = Arc::new(UnsafeCellWrapper::new(
+        Camera::new_fixed(config.clone(), &request.position, &request.rotation)
+    ));
+
+    // The world places the player at its spawn point, which the capture ignores
+    let mut player = Player::default();
+    let world = World::new(Arc::clone(&camera), &config, &mut player);
+
+    unsafe {
+        let camera = &*camera.get();
+        let world = &mut *world.get();
+        world.set_unsaved_time(request.time);
+        world.make_visible_meshes(camera);
+        world.render_world(&mut renderer, camera);
+        renderer.capture(camera, config.window_x as _, config.window_y as _, &request.file_path)
+    }
+}
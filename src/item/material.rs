@@ -15,6 +15,7 @@
 // limitations under the License.
 
 use lazy_static::lazy_static;
+use crate::player::hunger::FoodProperties;
 use crate::world::block::block_id::BlockId;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -29,7 +30,15 @@ pub enum ID {
     Cactus,
     Rose,
     TallGrass,
-    DeadShrub
+    DeadShrub,
+    Glass,
+    CoalOre,
+    IronOre,
+    GoldOre,
+    DiamondOre,
+    Gravel,
+    Apple,
+    Bread
 }
 
 /// @brief Determines case-by-case properties and behaviors of known block types.
@@ -37,7 +46,8 @@ pub struct Material {
     pub id: ID,
     pub max_stack_size: i32,
     pub is_block: bool,
-    pub name: String
+    pub name: String,
+    pub food: Option<FoodProperties>
 }
 
 impl Material {
@@ -51,10 +61,26 @@ impl Material {
             id,
             max_stack_size,
             is_block,
-            name: String::from(name)
+            name: String::from(name),
+            food: None
         }
     }
 
+    pub fn new_food(
+        id: ID,
+        max_stack_size: i32,
+        name: &str,
+        food: FoodProperties
+    ) -> Self {
+        let mut result = Self::new(id, max_stack_size, false, name);
+        result.food = Some(food);
+        result
+    }
+
+    pub fn is_food(&self) -> bool {
+        self.food.is_some()
+    }
+
     pub fn to_block_id(&self) -> BlockId {
         match self.id {
             ID::Nothing => BlockId::Air,
@@ -67,7 +93,14 @@ impl Material {
             ID::Cactus => BlockId::Cactus,
             ID::TallGrass => BlockId::TallGrass,
             ID::Rose => BlockId::Rose,
-            ID::DeadShrub => BlockId::DeadShrub
+            ID::DeadShrub => BlockId::DeadShrub,
+            ID::Glass => BlockId::Glass,
+            ID::CoalOre => BlockId::CoalOre,
+            ID::IronOre => BlockId::IronOre,
+            ID::GoldOre => BlockId::GoldOre,
+            ID::DiamondOre => BlockId::DiamondOre,
+            ID::Gravel => BlockId::Gravel,
+            ID::Apple | ID::Bread => BlockId::Air
         }
     }
 
@@ -83,6 +116,12 @@ impl Material {
             BlockId::Rose => &ROSE,
             BlockId::TallGrass => &TALL_GRASS,
             BlockId::DeadShrub => &DEAD_SHRUB,
+            BlockId::Glass => &GLASS_BLOCK,
+            BlockId::CoalOre => &COAL_ORE_BLOCK,
+            BlockId::IronOre => &IRON_ORE_BLOCK,
+            BlockId::GoldOre => &GOLD_ORE_BLOCK,
+            BlockId::DiamondOre => &DIAMOND_ORE_BLOCK,
+            BlockId::Gravel => &GRAVEL_BLOCK,
             _ => &NOTHING
         }
     }
@@ -123,4 +162,29 @@ lazy_static! {
     pub static ref DEAD_SHRUB: Material = Material::new(
         ID::DeadShrub, 99, true, "Dead Shrub"
     );
+    pub static ref GLASS_BLOCK: Material = Material::new(
+        ID::Glass, 99, true, "Glass Block"
+    );
+    pub static ref COAL_ORE_BLOCK: Material = Material::new(
+        ID::CoalOre, 99, true, "Coal Ore"
+    );
+    pub static ref IRON_ORE_BLOCK: Material = Material::new(
+        ID::IronOre, 99, true, "Iron Ore"
+    );
+    pub static ref GOLD_ORE_BLOCK: Material = Material::new(
+        ID::GoldOre, 99, true, "Gold Ore"
+    );
+    pub static ref DIAMOND_ORE_BLOCK: Material = Material::new(
+        ID::DiamondOre, 99, true, "Diamond Ore"
+    );
+    pub static ref GRAVEL_BLOCK: Material = Material::new(
+        ID::Gravel, 99, true, "Gravel Block"
+    );
+
+    pub static ref APPLE: Material = Material::new_food(
+        ID::Apple, 64, "Apple", FoodProperties::new(4, 0.3)
+    );
+    pub static ref BREAD: Material = Material::new_food(
+        ID::Bread, 64, "Bread", FoodProperties::new(5, 0.6)
+    );
 }
\ No newline at end of file
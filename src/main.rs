@@ -20,6 +20,7 @@ use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
 use crate::application::Application;
 use crate::config::Config;
+use crate::headless::CaptureRequest;
 
 pub mod application;
 pub mod states;
@@ -40,6 +41,7 @@ pub mod model;
 pub mod texture;
 pub mod shaders;
 pub mod gl;
+pub mod headless;
 
 /// @brief Self declared function that loads in configuration files as needed.
 /// @param config
@@ -74,6 +76,15 @@ fn load_config(config: &mut Config) {
             if key == "renderdistance" {
                 config.render_distance = parts[1].parse().unwrap();
                 log::debug!("Config: Render Distance: {}", config.render_distance);
+            } else if key == "loddistance" {
+                config.lod_distance = parts[1].parse().unwrap();
+                log::debug!("Config: LOD Distance: {}", config.lod_distance);
+            } else if key == "uploadbudget" {
+                config.upload_budget = parts[1].parse().unwrap();
+                log::debug!("Config: Upload budget: {} KB", config.upload_budget);
+            } else if key == "uploadtime" {
+                config.upload_time = parts[1].parse().unwrap();
+                log::debug!("Config: Upload time: {} ms", config.upload_time);
             } else if key == "fullscreen" {
                 let is_fullscreen: i32 = parts[1].parse().unwrap();
                 config.is_fullscreen = is_fullscreen == 1;
@@ -85,6 +96,51 @@ fn load_config(config: &mut Config) {
             } else if key == "fov" {
                 config.fov = parts[1].parse().unwrap();
                 log::debug!("Config: Field of Vision: {}", config.fov);
+            } else if key == "generator" {
+                config.terrain_generator = parts[1].to_string();
+                log::debug!("Config: Terrain generator: {}", config.terrain_generator);
+            } else if key == "time" {
+                config.world_time = Some(parts[1].parse().unwrap());
+                log::debug!("Config: World Time: {}", parts[1]);
+            } else if key == "texturepack" {
+                config.texture_pack = parts[1].to_string();
+                log::debug!("Config: Texture pack: {}", config.texture_pack);
+            } else if key == "texturearrays" {
+                let texture_arrays: i32 = parts[1].parse().unwrap();
+                config.texture_arrays = texture_arrays == 1;
+                log::debug!("Config: Texture arrays: {}", config.texture_arrays);
+            } else if key == "anisotropy" {
+                config.anisotropy = parts[1].parse().unwrap();
+                log::debug!("Config: Anisotropic filtering: {}", config.anisotropy);
+            } else if key == "hotreload" {
+                let hot_reload: i32 = parts[1].parse().unwrap();
+                config.hot_reload = hot_reload == 1;
+                log::debug!("Config: Hot reload: {}", config.hot_reload);
+            } else if key == "gammacorrection" {
+                let gamma_correction: i32 = parts[1].parse().unwrap();
+                config.gamma_correction = gamma_correction == 1;
+                log::debug!("Config: Gamma correction: {}", config.gamma_correction);
+            } else if key == "gamma" {
+                config.gamma = parts[1].parse().unwrap();
+                log::debug!("Config: Gamma: {}", config.gamma);
+            } else if key == "brightness" {
+                config.brightness = parts[1].parse().unwrap();
+                log::debug!("Config: Brightness: {}", config.brightness);
+            } else if key == "underwatereffect" {
+                let underwater_effect: i32 = parts[1].parse().unwrap();
+                config.underwater_effect = underwater_effect == 1;
+                log::debug!("Config: Underwater effect: {}", config.underwater_effect);
+            } else if key == "vignette" {
+                let vignette: i32 = parts[1].parse().unwrap();
+                config.vignette = vignette == 1;
+                log::debug!("Config: Vignette: {}", config.vignette);
+            } else if key == "fxaa" {
+                let fxaa: i32 = parts[1].parse().unwrap();
+                config.fxaa = fxaa == 1;
+                log::debug!("Config: FXAA: {}", config.fxaa);
+            } else if key == "colourblind" {
+                config.colour_blind_mode = parts[1].parse().unwrap();
+                log::debug!("Config: Colour blind mode: {}", config.colour_blind_mode);
             }
         }
     }
@@ -104,6 +160,13 @@ fn main() {
     load_config(&mut config);
     display_info();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(request) = CaptureRequest::from_args(&args) {
+        log::info!("Capturing {}...", request.file_path);
+        let is_saved = headless::capture(config, &request);
+        std::process::exit(if is_saved { 0 } else { 1 });
+    }
+
     log::info!("Loading game...");
 
     let app = Application::new(config);
@@ -95,6 +95,31 @@ impl NoiseGenerator {
         if val > 0.0 { val } else { 1.0 } // Compare if value is greater than 0
     }
 
+    /// @brief Smooth noise between -1 and 1 at a point in space, with one random value per
+    /// whole coordinate and interpolation in between.
+    /// @param x
+    /// @param y
+    /// @param z
+    pub fn get_noise_3d(&self, x: f64, y: f64, z: f64) -> f64 {
+        let floor_x = x.floor();
+        let floor_y = y.floor();
+        let floor_z = z.floor();
+
+        let corner = |dx: f64, dy: f64, dz: f64| {
+            self.get_noise_iii((floor_x + dx) as i32, (floor_y + dy) as i32, (floor_z + dz) as i32)
+        };
+
+        // Along x on the four edges, then along z, then along y
+        let bottom_near = Self::lerp(corner(0., 0., 0.), corner(1., 0., 0.), x - floor_x);
+        let bottom_far = Self::lerp(corner(0., 0., 1.), corner(1., 0., 1.), x - floor_x);
+        let top_near = Self::lerp(corner(0., 1., 0.), corner(1., 1., 0.), x - floor_x);
+        let top_far = Self::lerp(corner(0., 1., 1.), corner(1., 1., 1.), x - floor_x);
+
+        let bottom = Self::lerp(bottom_near, bottom_far, z - floor_z);
+        let top = Self::lerp(top_near, top_far, z - floor_z);
+        Self::lerp(bottom, top, y - floor_y)
+    }
+
     pub fn set_parameters(&mut self, params: NoiseParameters) {
         self.noise_parameters = params;
     }
@@ -119,6 +144,14 @@ impl NoiseGenerator {
         self.get_noise_i((x + z * 57.0) as i32)
     }
 
+    /// @brief Noise of a point on the integer grid, spreading the coordinates with large primes
+    /// so that neighbouring points do not repeat each other.
+    fn get_noise_iii(&self, x: i32, y: i32, z: i32) -> f64 {
+        self.get_noise_i(
+            x.wrapping_mul(1619).wrapping_add(y.wrapping_mul(31337)).wrapping_add(z.wrapping_mul(6971))
+        )
+    }
+
     fn lerp(a: f64, b: f64, z: f64) -> f64 {
         let mu2 = (1.0 - (z * 3.14).cos()) / 2.0;
         a * (1.0 - mu2) + b * mu2
@@ -16,6 +16,8 @@
 
 extern crate nalgebra_glm as glm;
 
+use nalgebra_glm::IVec3;
+
 /// @brief Raycasting class associated with player's line of sight.
 pub struct Ray {
     ray_start: glm::TVec3<f32>,
@@ -48,4 +50,134 @@ impl Ray {
     pub fn length(&self) -> f32 {
         glm::distance(&self.ray_start, &self.ray_end)
     }
+
+    /// @brief Unit vector the ray points at, derived from the pitch (x) and yaw (y) rotation.
+    pub fn direction_vector(&self) -> glm::TVec3<f32> {
+        direction_from_rotation(&self.direction)
+    }
+
+    /// @brief Casts the ray from its start through the voxel grid.
+    /// @param max_distance
+    /// @param is_solid
+    /// @return hit
+    pub fn cast<F>(&self, max_distance: f32, is_solid: F) -> Option<RayHit>
+    where
+        F: FnMut(i32, i32, i32) -> bool
+    {
+        voxel_traversal(&self.ray_start, &self.direction_vector(), max_distance, is_solid)
+    }
+}
+
+/// @brief Block hit by a voxel ray cast.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RayHit {
+    /// Position of the block that was hit.
+    pub block_position: IVec3,
+    /// Normal of the face the ray entered the block through. Zero if the ray started inside it.
+    pub normal: IVec3,
+    /// Distance travelled along the ray before hitting the block.
+    pub distance: f32,
+    /// Exact point where the ray entered the block.
+    pub position: glm::TVec3<f32>
+}
+
+impl RayHit {
+    /// @brief Position of the block adjacent to the hit face, e.g. where a block would be placed.
+    pub fn adjacent_position(&self) -> IVec3 {
+        self.block_position + self.normal
+    }
+}
+
+/// @brief Converts a rotation in degrees (x = pitch, y = yaw) into a unit direction vector.
+/// @param rotation
+pub fn direction_from_rotation(rotation: &glm::TVec3<f32>) -> glm::TVec3<f32> {
+    let yaw = (rotation.y + 90.0).to_radians();
+    let pitch = rotation.x.to_radians();
+
+    glm::vec3(
+        -yaw.cos() * pitch.cos(),
+        -pitch.sin(),
+        -yaw.sin() * pitch.cos()
+    )
+}
+
+/// @brief Walks every voxel the ray passes through, in order, until `is_solid` accepts one
+/// (Amanatides & Woo, "A Fast Voxel Traversal Algorithm for Ray Tracing").
+/// @param origin
+/// @param direction
+/// @param max_distance
+/// @param is_solid
+/// @return hit
+pub fn voxel_traversal<F>(
+    origin: &glm::TVec3<f32>,
+    direction: &glm::TVec3<f32>,
+    max_distance: f32,
+    mut is_solid: F
+) -> Option<RayHit>
+where
+    F: FnMut(i32, i32, i32) -> bool
+{
+    if glm::length(direction) == 0.0 {
+        return None;
+    }
+    let direction = glm::normalize(direction);
+
+    let mut block = IVec3::new(
+        origin.x.floor() as i32,
+        origin.y.floor() as i32,
+        origin.z.floor() as i32
+    );
+    if is_solid(block.x, block.y, block.z) {
+        return Some(RayHit {
+            block_position: block,
+            normal: IVec3::zeros(),
+            distance: 0.0,
+            position: *origin
+        });
+    }
+
+    let mut step = IVec3::zeros();
+    // Distance along the ray to cross one whole voxel, and to reach the next boundary, per axis.
+    let mut t_delta = glm::vec3(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+    let mut t_max = glm::vec3(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+    for axis in 0..3 {
+        if direction[axis] > 0.0 {
+            step[axis] = 1;
+            t_delta[axis] = 1.0 / direction[axis];
+            t_max[axis] = (block[axis] as f32 + 1.0 - origin[axis]) / direction[axis];
+        } else if direction[axis] < 0.0 {
+            step[axis] = -1;
+            t_delta[axis] = -1.0 / direction[axis];
+            t_max[axis] = (origin[axis] - block[axis] as f32) / -direction[axis];
+        }
+    }
+
+    loop {
+        let axis = if t_max.x < t_max.y {
+            if t_max.x < t_max.z { 0 } else { 2 }
+        } else if t_max.y < t_max.z {
+            1
+        } else {
+            2
+        };
+
+        let distance = t_max[axis];
+        if distance > max_distance {
+            return None;
+        }
+
+        block[axis] += step[axis];
+        t_max[axis] += t_delta[axis];
+
+        if is_solid(block.x, block.y, block.z) {
+            let mut normal = IVec3::zeros();
+            normal[axis] = -step[axis];
+            return Some(RayHit {
+                block_position: block,
+                normal,
+                distance,
+                position: origin + direction * distance
+            });
+        }
+    }
 }
\ No newline at end of file
@@ -25,7 +25,8 @@ pub struct Model {
     render_info: RenderInfo,
 
     vbo_count: i32,
-    buffers: Vec<GLuint>
+    buffers: Vec<GLuint>,
+    ebo: GLuint
 }
 
 impl Model {
@@ -59,6 +60,7 @@ impl Model {
         self.buffers.clear();
 
         self.vbo_count = 0;
+        self.ebo = 0;
         self.render_info.reset();
     }
 
@@ -86,6 +88,9 @@ impl Model {
                 gl::STATIC_DRAW
             );
         }
+
+        self.ebo = ebo;
+        self.buffers.push(ebo);
     }
 
     pub fn add_vbo(&mut self, dimensions: i32, data: &Vec<GLfloat>) {
@@ -31,10 +31,57 @@ impl AABB {
         }
     }
 
+    pub fn new_with_position(position: &glm::TVec3<f32>, dim: &glm::TVec3<f32>) -> Self {
+        Self {
+            position: *position,
+            dimensions: *dim
+        }
+    }
+
     pub fn update(&mut self, location: &glm::TVec3<f32>) {
         self.position = *location;
     }
 
+    pub fn min(&self) -> glm::TVec3<f32> {
+        self.position
+    }
+
+    pub fn max(&self) -> glm::TVec3<f32> {
+        self.position + self.dimensions
+    }
+
+    pub fn centre(&self) -> glm::TVec3<f32> {
+        self.position + self.dimensions / 2.0
+    }
+
+    pub fn offset(&self, offset: &glm::TVec3<f32>) -> Self {
+        Self::new_with_position(&(self.position + offset), &self.dimensions)
+    }
+
+    /// @brief Grows the box towards the direction of the given motion, covering the whole sweep.
+    /// @param motion
+    pub fn expand_towards(&self, motion: &glm::TVec3<f32>) -> Self {
+        let mut result = *self;
+        for axis in 0..3 {
+            if motion[axis] < 0.0 {
+                result.position[axis] += motion[axis];
+                result.dimensions[axis] -= motion[axis];
+            } else {
+                result.dimensions[axis] += motion[axis];
+            }
+        }
+        result
+    }
+
+    pub fn intersects(&self, other: &AABB) -> bool {
+        let (min, max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+
+        min.x < other_max.x && max.x > other_min.x &&
+            min.y < other_max.y && max.y > other_min.y &&
+            min.z < other_max.z && max.z > other_min.z
+    }
+
     pub fn get_vn(&self, normal: glm::TVec3<f32>) -> glm::TVec3<f32> {
         let mut res = self.position;
 
@@ -14,4 +14,5 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub mod aabb;
\ No newline at end of file
+pub mod aabb;
+pub mod voxel_collision;
\ No newline at end of file
@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use crate::physics::aabb::AABB;
+
+/// Tolerance used when comparing box faces, so that resting contacts stay resting.
+const EPSILON: f32 = 1e-4;
+
+/// @brief Outcome of moving a box through the voxel grid.
+#[derive(Clone, Default, Debug)]
+pub struct CollisionResult {
+    /// Motion that could actually be applied without penetrating any block.
+    pub motion: glm::TVec3<f32>,
+    /// Normals of the block faces that stopped the motion, one per blocked axis.
+    pub normals: Vec<glm::TVec3<f32>>,
+    /// Whether the box was lifted onto a ledge to complete its horizontal motion.
+    pub stepped: bool
+}
+
+impl CollisionResult {
+    pub fn is_on_ground(&self) -> bool {
+        self.normals.iter().any(|normal| normal.y > 0.0)
+    }
+
+    pub fn hit_ceiling(&self) -> bool {
+        self.normals.iter().any(|normal| normal.y < 0.0)
+    }
+
+    /// @brief Whether the motion along the given axis (0 = X, 1 = Y, 2 = Z) was blocked.
+    /// @param axis
+    pub fn is_blocked(&self, axis: usize) -> bool {
+        self.normals.iter().any(|normal| normal[axis] != 0.0)
+    }
+}
+
+/// @brief Sweeps `box_` by `motion` through the voxel grid and clips the motion against every
+/// collision box on the way.
+///
+/// All boxes touched by the whole sweep are gathered up front and the motion is resolved one
+/// axis at a time (Y first, then X and Z), so fast movement cannot tunnel through thin walls.
+/// When the horizontal motion is blocked, the box is allowed to climb ledges of up to
+/// `step_height`; pass `0.0` to disable stepping (e.g. while airborne).
+///
+/// `get_collision_boxes` returns the collision boxes, in world space, of the block at the given
+/// block coordinates.
+/// @param box_
+/// @param motion
+/// @param step_height
+/// @param get_collision_boxes
+/// @return result
+pub fn move_and_collide<F>(
+    box_: &AABB,
+    motion: &glm::TVec3<f32>,
+    step_height: f32,
+    mut get_collision_boxes: F
+) -> CollisionResult
+where
+    F: FnMut(i32, i32, i32) -> Vec<AABB>
+{
+    let sweep = box_.expand_towards(motion)
+        .expand_towards(&glm::vec3(0.0, step_height.max(0.0), 0.0));
+    let obstacles = gather_boxes(&sweep, &mut get_collision_boxes);
+
+    let mut result = resolve(box_, motion, &obstacles);
+
+    let horizontal_blocked = result.is_blocked(0) || result.is_blocked(2);
+    if step_height > 0.0 && horizontal_blocked {
+        // Lift, move horizontally, then settle back down onto whatever is underneath.
+        let up = resolve(box_, &glm::vec3(0.0, step_height, 0.0), &obstacles);
+        let lifted = box_.offset(&up.motion);
+        let across = resolve(
+            &lifted,
+            &glm::vec3(motion.x, 0.0, motion.z),
+            &obstacles
+        );
+        let moved = lifted.offset(&across.motion);
+        let down = resolve(
+            &moved,
+            &glm::vec3(0.0, -up.motion.y + motion.y.min(0.0), 0.0),
+            &obstacles
+        );
+
+        let stepped_motion = up.motion + across.motion + down.motion;
+        let stepped_distance = stepped_motion.x * stepped_motion.x + stepped_motion.z * stepped_motion.z;
+        let direct_distance = result.motion.x * result.motion.x + result.motion.z * result.motion.z;
+
+        // Only accept the step if it ends on solid ground and gets us further.
+        if down.is_on_ground() && stepped_distance > direct_distance + EPSILON {
+            let mut normals = across.normals;
+            normals.extend(down.normals);
+            result = CollisionResult {
+                motion: stepped_motion,
+                normals,
+                stepped: true
+            };
+        }
+    }
+
+    result
+}
+
+fn gather_boxes<F>(sweep: &AABB, get_collision_boxes: &mut F) -> Vec<AABB>
+where
+    F: FnMut(i32, i32, i32) -> Vec<AABB>
+{
+    let min = sweep.min();
+    let max = sweep.max();
+
+    let mut boxes = Vec::new();
+    for x in min.x.floor() as i32..=max.x.floor() as i32 {
+        for y in min.y.floor() as i32..=max.y.floor() as i32 {
+            for z in min.z.floor() as i32..=max.z.floor() as i32 {
+                boxes.extend(get_collision_boxes(x, y, z));
+            }
+        }
+    }
+    boxes
+}
+
+fn resolve(box_: &AABB, motion: &glm::TVec3<f32>, obstacles: &[AABB]) -> CollisionResult {
+    let mut result = CollisionResult::default();
+    let mut current = *box_;
+
+    for axis in [1, 0, 2] {
+        let wanted = motion[axis];
+        if wanted == 0.0 {
+            continue;
+        }
+
+        let clipped = obstacles.iter()
+            .fold(wanted, |distance, obstacle| clip_axis(&current, obstacle, axis, distance));
+
+        if (clipped - wanted).abs() > EPSILON * 0.5 {
+            let mut normal = glm::vec3(0.0, 0.0, 0.0);
+            normal[axis] = -wanted.signum();
+            result.normals.push(normal);
+        }
+
+        result.motion[axis] = clipped;
+        let mut offset = glm::vec3(0.0, 0.0, 0.0);
+        offset[axis] = clipped;
+        current = current.offset(&offset);
+    }
+
+    result
+}
+
+/// @brief Clips `distance` along `axis` so that `moving` does not enter `obstacle`.
+fn clip_axis(moving: &AABB, obstacle: &AABB, axis: usize, distance: f32) -> f32 {
+    let (min, max) = (moving.min(), moving.max());
+    let (other_min, other_max) = (obstacle.min(), obstacle.max());
+
+    // Boxes only collide along an axis if they overlap on the two others.
+    for other_axis in 0..3 {
+        if other_axis == axis {
+            continue;
+        }
+        if max[other_axis] <= other_min[other_axis] + EPSILON ||
+            min[other_axis] >= other_max[other_axis] - EPSILON {
+            return distance;
+        }
+    }
+
+    if distance > 0.0 && max[axis] <= other_min[axis] + EPSILON {
+        distance.min(other_min[axis] - max[axis])
+    } else if distance < 0.0 && min[axis] >= other_max[axis] - EPSILON {
+        distance.max(other_max[axis] - min[axis])
+    } else {
+        distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER_SIZE: f32 = 0.6;
+    const PLAYER_HEIGHT: f32 = 1.8;
+
+    fn player_at(x: f32, y: f32, z: f32) -> AABB {
+        AABB::new_with_position(&glm::vec3(x, y, z), &glm::vec3(PLAYER_SIZE, PLAYER_HEIGHT, PLAYER_SIZE))
+    }
+
+    /// @brief Collision boxes of a world where `is_solid` blocks are full cubes, except
+    /// `slab` blocks, which are half a block high.
+    fn world(
+        is_solid: impl Fn(i32, i32, i32) -> bool,
+        is_slab: impl Fn(i32, i32, i32) -> bool
+    ) -> impl FnMut(i32, i32, i32) -> Vec<AABB> {
+        move |x, y, z| {
+            let position = glm::vec3(x as f32, y as f32, z as f32);
+            if is_slab(x, y, z) {
+                vec![AABB::new_with_position(&position, &glm::vec3(1.0, 0.5, 1.0))]
+            } else if is_solid(x, y, z) {
+                vec![AABB::new_with_position(&position, &glm::vec3(1.0, 1.0, 1.0))]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    fn assert_near(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn fast_motion_does_not_tunnel_through_thin_wall() {
+        let result = move_and_collide(
+            &player_at(0.2, 1.0, 0.2),
+            &glm::vec3(10.0, 0.0, 0.0),
+            0.0,
+            world(|x, y, _| x == 3 && (1..=2).contains(&y), |_, _, _| false)
+        );
+
+        assert_near(result.motion.x, 3.0 - 0.2 - PLAYER_SIZE);
+        assert!(result.is_blocked(0));
+        assert_eq!(result.normals, vec![glm::vec3(-1.0, 0.0, 0.0)]);
+    }
+
+    #[test]
+    fn resting_on_floor_reports_upward_normal() {
+        let result = move_and_collide(
+            &player_at(0.2, 1.0, 0.2),
+            &glm::vec3(0.0, -0.08, 0.0),
+            0.0,
+            world(|_, y, _| y == 0, |_, _, _| false)
+        );
+
+        assert_near(result.motion.y, 0.0);
+        assert!(result.is_on_ground());
+        assert_eq!(result.normals, vec![glm::vec3(0.0, 1.0, 0.0)]);
+    }
+
+    #[test]
+    fn steps_up_onto_half_block() {
+        let result = move_and_collide(
+            &player_at(0.2, 1.0, 0.2),
+            &glm::vec3(0.5, 0.0, 0.0),
+            0.6,
+            world(|_, y, _| y == 0, |x, y, _| x == 1 && y == 1)
+        );
+
+        assert!(result.stepped);
+        assert!(result.is_on_ground());
+        assert_near(result.motion.x, 0.5);
+        assert_near(result.motion.y, 0.5);
+    }
+
+    #[test]
+    fn does_not_step_up_full_block() {
+        let result = move_and_collide(
+            &player_at(0.2, 1.0, 0.2),
+            &glm::vec3(0.5, 0.0, 0.0),
+            0.6,
+            world(|x, y, _| y == 0 || (x == 1 && y == 1), |_, _, _| false)
+        );
+
+        assert!(!result.stepped);
+        assert!(result.is_blocked(0));
+        assert_near(result.motion.x, 1.0 - 0.2 - PLAYER_SIZE);
+        assert_near(result.motion.y, 0.0);
+    }
+
+    #[test]
+    fn hits_ceiling() {
+        let result = move_and_collide(
+            &player_at(0.2, 1.0, 0.2),
+            &glm::vec3(0.0, 1.0, 0.0),
+            0.0,
+            world(|_, y, _| y == 3, |_, _, _| false)
+        );
+
+        assert!(result.hit_ceiling());
+        assert!(!result.is_on_ground());
+        assert_near(result.motion.y, 3.0 - 1.0 - PLAYER_HEIGHT);
+    }
+
+    #[test]
+    fn slides_along_wall_on_diagonal_motion() {
+        let result = move_and_collide(
+            &player_at(0.2, 1.0, 0.2),
+            &glm::vec3(0.5, 0.0, 0.5),
+            0.0,
+            world(|x, _, _| x == 1, |_, _, _| false)
+        );
+
+        assert!(result.is_blocked(0));
+        assert!(!result.is_blocked(2));
+        assert_near(result.motion.x, 1.0 - 0.2 - PLAYER_SIZE);
+        assert_near(result.motion.z, 0.5);
+    }
+}
@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub const MAX_HEALTH: f32 = 20.0;
+pub const MAX_FOOD_LEVEL: i32 = 20;
+
+/// Exhaustion that has to build up before a point of saturation (or food) is consumed.
+pub const EXHAUSTION_THRESHOLD: f32 = 4.0;
+
+pub const EXHAUSTION_SPRINT_PER_BLOCK: f32 = 0.1;
+pub const EXHAUSTION_JUMP: f32 = 0.05;
+pub const EXHAUSTION_SPRINT_JUMP: f32 = 0.2;
+pub const EXHAUSTION_BLOCK_BREAK: f32 = 0.005;
+pub const EXHAUSTION_REGENERATION: f32 = 6.0;
+
+/// Food level required before health regenerates on its own.
+pub const REGENERATION_FOOD_LEVEL: i32 = 18;
+/// Food level at (or below) which the player is too hungry to sprint.
+pub const SPRINT_FOOD_LEVEL: i32 = 6;
+
+/// Seconds between two natural regeneration (or starvation) steps.
+pub const HEALTH_TICK_INTERVAL: f32 = 4.0;
+
+/// Starvation never takes the player below this much health.
+pub const STARVATION_MIN_HEALTH: f32 = 1.0;
+
+/// @brief Nutrition restored by eating a food item.
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct FoodProperties {
+    pub nutrition: i32,
+    pub saturation_modifier: f32
+}
+
+/// @brief Hunger, saturation and exhaustion bookkeeping of a player.
+///
+/// Actions add exhaustion; once it passes `EXHAUSTION_THRESHOLD` the saturation
+/// buffer is drained first and the food level afterwards. The food level then
+/// decides whether health regenerates or the player starves.
+#[derive(Copy, Clone, Debug)]
+pub struct HungerStats {
+    food_level: i32,
+    saturation: f32,
+    exhaustion: f32,
+
+    health_timer: f32
+}
+
+impl FoodProperties {
+    pub fn new(nutrition: i32, saturation_modifier: f32) -> Self {
+        Self { nutrition, saturation_modifier }
+    }
+
+    /// @brief Saturation granted on top of the nutrition itself.
+    pub fn saturation(&self) -> f32 {
+        self.nutrition as f32 * self.saturation_modifier * 2.0
+    }
+}
+
+impl HungerStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn food_level(&self) -> i32 {
+        self.food_level
+    }
+
+    pub fn saturation(&self) -> f32 {
+        self.saturation
+    }
+
+    pub fn exhaustion(&self) -> f32 {
+        self.exhaustion
+    }
+
+    pub fn can_sprint(&self) -> bool {
+        self.food_level > SPRINT_FOOD_LEVEL
+    }
+
+    pub fn needs_food(&self) -> bool {
+        self.food_level < MAX_FOOD_LEVEL
+    }
+
+    pub fn add_exhaustion(&mut self, amount: f32) {
+        self.exhaustion += amount;
+
+        while self.exhaustion >= EXHAUSTION_THRESHOLD {
+            self.exhaustion -= EXHAUSTION_THRESHOLD;
+            if self.saturation > 0.0 {
+                self.saturation = (self.saturation - 1.0).max(0.0);
+            } else if self.food_level > 0 {
+                self.food_level -= 1;
+            }
+        }
+    }
+
+    /// @brief Restores food level and saturation. Saturation can never exceed the food level.
+    /// @param food
+    pub fn eat(&mut self, food: &FoodProperties) {
+        self.food_level = (self.food_level + food.nutrition).min(MAX_FOOD_LEVEL);
+        self.saturation = (self.saturation + food.saturation()).min(self.food_level as f32);
+    }
+
+    /// @brief Advances the regeneration/starvation timer and applies it to the given health.
+    /// @param dt
+    /// @param health
+    /// @return Health difference applied during this update.
+    pub fn update(&mut self, dt: f32, health: &mut f32) -> f32 {
+        let old_health = *health;
+        let regenerating = self.food_level >= REGENERATION_FOOD_LEVEL && *health < MAX_HEALTH;
+        let starving = self.food_level <= 0 && *health > STARVATION_MIN_HEALTH;
+
+        if !regenerating && !starving {
+            self.health_timer = 0.0;
+            return 0.0;
+        }
+
+        self.health_timer += dt;
+        while self.health_timer >= HEALTH_TICK_INTERVAL {
+            self.health_timer -= HEALTH_TICK_INTERVAL;
+
+            if self.food_level >= REGENERATION_FOOD_LEVEL && *health < MAX_HEALTH {
+                *health = (*health + 1.0).min(MAX_HEALTH);
+                self.add_exhaustion(EXHAUSTION_REGENERATION);
+            } else if self.food_level <= 0 && *health > STARVATION_MIN_HEALTH {
+                *health = (*health - 1.0).max(STARVATION_MIN_HEALTH);
+            }
+        }
+
+        *health - old_health
+    }
+}
+
+impl Default for HungerStats {
+    fn default() -> Self {
+        Self {
+            food_level: MAX_FOOD_LEVEL,
+            saturation: 5.0,
+            exhaustion: 0.0,
+            health_timer: 0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 0.5;
+
+    fn hunger(food_level: i32, saturation: f32) -> HungerStats {
+        HungerStats {
+            food_level,
+            saturation,
+            ..HungerStats::default()
+        }
+    }
+
+    /// @brief Runs `update` with the fixed `DT` for the given number of seconds.
+    fn run(stats: &mut HungerStats, health: &mut f32, seconds: f32) -> f32 {
+        let steps = (seconds / DT).round() as i32;
+        (0..steps).map(|_| stats.update(DT, health)).sum()
+    }
+
+    #[test]
+    fn exhaustion_drains_saturation_then_food() {
+        let mut stats = hunger(MAX_FOOD_LEVEL, 1.5);
+
+        stats.add_exhaustion(EXHAUSTION_THRESHOLD - 0.5);
+        assert_eq!(stats.saturation(), 1.5);
+        assert_eq!(stats.food_level(), MAX_FOOD_LEVEL);
+
+        stats.add_exhaustion(1.0);
+        assert_eq!(stats.saturation(), 0.5);
+        assert_eq!(stats.exhaustion(), 0.5);
+
+        // The last half point of saturation absorbs a whole step
+        stats.add_exhaustion(EXHAUSTION_THRESHOLD);
+        assert_eq!(stats.saturation(), 0.0);
+        assert_eq!(stats.food_level(), MAX_FOOD_LEVEL);
+
+        stats.add_exhaustion(EXHAUSTION_THRESHOLD * 2.0);
+        assert_eq!(stats.food_level(), MAX_FOOD_LEVEL - 2);
+    }
+
+    #[test]
+    fn eating_clamps_food_level_and_saturation() {
+        let mut stats = hunger(MAX_FOOD_LEVEL - 2, 0.0);
+        stats.eat(&FoodProperties::new(6, 0.6));
+        assert_eq!(stats.food_level(), MAX_FOOD_LEVEL);
+        assert!((stats.saturation() - 7.2).abs() < 1e-4);
+
+        // Saturation never exceeds the food level
+        let mut stats = hunger(2, 0.0);
+        stats.eat(&FoodProperties::new(4, 1.2));
+        assert_eq!(stats.food_level(), 6);
+        assert_eq!(stats.saturation(), 6.0);
+    }
+
+    #[test]
+    fn regenerates_only_above_food_threshold() {
+        let mut stats = hunger(REGENERATION_FOOD_LEVEL - 1, 5.0);
+        let mut health = 10.0;
+        assert_eq!(run(&mut stats, &mut health, HEALTH_TICK_INTERVAL * 3.0), 0.0);
+        assert_eq!(health, 10.0);
+
+        let mut stats = hunger(REGENERATION_FOOD_LEVEL, 5.0);
+        assert_eq!(run(&mut stats, &mut health, HEALTH_TICK_INTERVAL), 1.0);
+        assert_eq!(health, 11.0);
+        // Healing costs exhaustion, taken out of the saturation
+        assert_eq!(stats.saturation(), 4.0);
+        assert_eq!(stats.exhaustion(), EXHAUSTION_REGENERATION - EXHAUSTION_THRESHOLD);
+
+        let mut health = MAX_HEALTH;
+        assert_eq!(run(&mut stats, &mut health, HEALTH_TICK_INTERVAL * 3.0), 0.0);
+    }
+
+    #[test]
+    fn starves_at_zero_food_down_to_minimum_health() {
+        let mut stats = hunger(0, 0.0);
+        let mut health = 3.0;
+
+        assert_eq!(run(&mut stats, &mut health, HEALTH_TICK_INTERVAL - DT), 0.0);
+        assert_eq!(run(&mut stats, &mut health, DT), -1.0);
+        assert_eq!(health, 2.0);
+
+        run(&mut stats, &mut health, HEALTH_TICK_INTERVAL * 10.0);
+        assert_eq!(health, STARVATION_MIN_HEALTH);
+    }
+}
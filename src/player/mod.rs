@@ -14,4 +14,5 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub mod player;
\ No newline at end of file
+pub mod player;
+pub mod hunger;
\ No newline at end of file
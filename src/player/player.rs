@@ -25,10 +25,17 @@ use sfml::window::{Key, Window};
 use crate::entity::Entity;
 use crate::input::keyboard::Keyboard;
 use crate::input::toggle_key::ToggleKey;
+use crate::physics::aabb::AABB;
+use crate::physics::voxel_collision;
 use crate::item::item_stack::ItemStack;
 use crate::item::material;
 use crate::item::material::{ID, Material};
+use crate::maths::ray::RayHit;
+use crate::player::hunger;
+use crate::player::hunger::HungerStats;
 use crate::renderer::render_master::RenderMaster;
+use crate::renderer::text_overlay_renderer::OverlayText;
+use crate::world::block::block_data::BlockShaderType;
 use crate::world::world::World;
 
 pub struct Player<'a> {
@@ -37,6 +44,14 @@ pub struct Player<'a> {
     is_on_ground: bool,
     is_flying: bool,
     is_sneak: bool,
+    is_sprinting: bool,
+    is_in_water: bool,
+    is_eyes_in_water: bool,
+
+    health: f32,
+    hunger: HungerStats,
+    air: f32,
+    drown_timer: f32,
 
     items: Vec<ItemStack>,
     item_text: Vec<Text<'a>>,
@@ -46,6 +61,7 @@ pub struct Player<'a> {
     item_down: ToggleKey,
     item_up: ToggleKey,
     fly_key: ToggleKey,
+    eat_key: ToggleKey,
 
     num1: ToggleKey,
     num2: ToggleKey,
@@ -65,6 +81,26 @@ static mut LAST_MOUSE_POSITION_PTR: *mut Vector2i = ptr::null_mut();
 
 const SPEED: f32 = 0.2;
 
+// Height of the player's box above its position, and the highest ledge it walks up without jumping.
+const HEAD_HEIGHT: f32 = 0.7;
+const STEP_HEIGHT: f32 = 0.55;
+
+// Offset from the player position to the feet and eyes respectively.
+const FEET_OFFSET: f32 = -0.9;
+const EYE_OFFSET: f32 = 0.6;
+
+const WATER_GRAVITY_FACTOR: f32 = 0.15;
+const WATER_MOVEMENT_FACTOR: f32 = 0.4;
+const WATER_DRAG: f32 = 4.;
+const WATER_MAX_SINK_SPEED: f32 = 3.;
+const WATER_MAX_SWIM_SPEED: f32 = 4.;
+
+/// Seconds the player can stay with the eyes under water before drowning.
+pub const MAX_AIR: f32 = 15.;
+const AIR_REFILL_RATE: f32 = 5.;
+const DROWN_DAMAGE_INTERVAL: f32 = 1.;
+const DROWN_DAMAGE: f32 = 2.;
+
 impl<'a> Player<'a> {
     pub fn handle_input(&mut self, window: &mut Window, keyboard: &Keyboard) {
         self.keyboard_input(keyboard);
@@ -86,6 +122,10 @@ impl<'a> Player<'a> {
             self.is_flying = !self.is_flying;
         }
 
+        if self.eat_key.is_key_pressed() {
+            self.eat_held_item();
+        }
+
         if self.num1.is_key_pressed() {
             self.held_item = 0;
         }
@@ -107,28 +147,39 @@ impl<'a> Player<'a> {
     }
 
     pub fn update(&mut self, dt: f32, world: &mut World) {
+        self.update_fluid_state(world);
+
+        if self.is_in_water && !self.is_flying {
+            self.acceleration.x *= WATER_MOVEMENT_FACTOR;
+            self.acceleration.z *= WATER_MOVEMENT_FACTOR;
+        }
         self.base.velocity += self.acceleration;
         self.acceleration = glm::vec3(0., 0., 0.);
 
         if !self.is_flying {
-            if !self.is_on_ground {
+            if self.is_in_water {
+                // Buoyancy mostly cancels out gravity, and the water drags on every axis.
+                self.base.velocity.y -= 40. * WATER_GRAVITY_FACTOR * dt;
+                self.base.velocity *= (1. - WATER_DRAG * dt).max(0.);
+                self.base.velocity.y = self.base.velocity.y
+                    .clamp(-WATER_MAX_SINK_SPEED, WATER_MAX_SWIM_SPEED);
+            } else {
                 self.base.velocity.y -= 40. * dt;
             }
-            self.is_on_ground = false;
         }
 
         if self.base.position.y <= 0. && !self.is_flying {
             self.base.position.y = 300.;
         }
 
-        self.base.position.x += self.base.velocity.x * dt;
-        self.collide(world, &glm::vec3(self.base.velocity.x, 0., 0.), dt);
-
-        self.base.position.y += self.base.velocity.y * dt;
-        self.collide(world, &glm::vec3(0., self.base.velocity.y, 0.), dt);
+        self.collide(world, dt);
 
-        self.base.position.z += self.base.velocity.z * dt;
-        self.collide(world, &glm::vec3(0., 0., self.base.velocity.z), dt);
+        if self.is_sprinting && !self.is_flying {
+            let distance = glm::length(&glm::vec2(self.base.velocity.x, self.base.velocity.z)) * dt;
+            self.hunger.add_exhaustion(distance * hunger::EXHAUSTION_SPRINT_PER_BLOCK);
+        }
+        self.hunger.update(dt, &mut self.health);
+        self.update_air(dt);
 
         self.base.box_aabb.update(&self.base.position);
         self.base.velocity.x *= 0.95;
@@ -138,43 +189,40 @@ impl<'a> Player<'a> {
         }
     }
 
-    pub fn collide(&mut self, world: &mut World, vel: &glm::TVec3<f32>, _dt: f32) {
-        let mut x = (self.base.position.x - self.base.box_aabb.dimensions.x) as i32;
-        while (x as f32) < self.base.position.x + self.base.box_aabb.dimensions.x {
-            let mut y = (self.base.position.y - self.base.box_aabb.dimensions.y) as i32;
-            while (y as f32) < self.base.position.y + 0.7 {
-                let mut z = (self.base.position.z - self.base.box_aabb.dimensions.z) as i32;
-                while (z as f32) < self.base.position.z + self.base.box_aabb.dimensions.z {
-                    let block = world.get_block(x, y, z);
-
-                    if block.id != 0 && block.get_data().read().unwrap().block_data().is_collidable {
-                        if vel.y > 0. {
-                            self.base.position.y = y as f32 - self.base.box_aabb.dimensions.y;
-                            self.base.velocity.y = 0.;
-                        } else if vel.y < 0. {
-                            self.is_on_ground = true;
-                            self.base.position.y = y as f32 + self.base.box_aabb.dimensions.y + 1.;
-                            self.base.velocity.y = 0.;
-                        }
-
-                        if vel.x > 0. {
-                            self.base.position.x = x as f32 - self.base.box_aabb.dimensions.x;
-                        } else if vel.x < 0. {
-                            self.base.position.x = x as f32 + self.base.box_aabb.dimensions.x + 1.;
-                        }
-
-                        if vel.z > 0. {
-                            self.base.position.z = z as f32 - self.base.box_aabb.dimensions.z;
-                        } else if vel.z < 0. {
-                            self.base.position.z = z as f32 + self.base.box_aabb.dimensions.z + 1.;
-                        }
-                    }
-                    z += 1;
-                }
-                y += 1;
+    /// @brief Moves the player by its velocity, resolving collisions against the world.
+    /// @param world
+    /// @param dt
+    pub fn collide(&mut self, world: &mut World, dt: f32) {
+        let motion = self.base.velocity * dt;
+        let step_height = if self.is_on_ground && !self.is_flying { STEP_HEIGHT } else { 0. };
+
+        let result = voxel_collision::move_and_collide(
+            &self.get_collision_box(),
+            &motion,
+            step_height,
+            |x, y, z| world.get_collision_boxes(x, y, z)
+        );
+
+        self.base.position += result.motion;
+        for axis in 0..3 {
+            if result.is_blocked(axis) {
+                self.base.velocity[axis] = 0.;
             }
-            x += 1;
         }
+        self.is_on_ground = result.is_on_ground();
+    }
+
+    /// @brief World space box enclosing the player, from the feet up to the top of the head.
+    pub fn get_collision_box(&self) -> AABB {
+        let dimensions = &self.base.box_aabb.dimensions;
+        AABB::new_with_position(
+            &glm::vec3(
+                self.base.position.x - dimensions.x,
+                self.base.position.y - dimensions.y,
+                self.base.position.z - dimensions.z
+            ),
+            &glm::vec3(dimensions.x * 2., dimensions.y + HEAD_HEIGHT, dimensions.z * 2.)
+        )
     }
 
     pub fn add_item(&mut self, material: &'static Material) {
@@ -191,7 +239,7 @@ impl<'a> Player<'a> {
         }
     }
 
-    pub fn draw(&mut self, _master: &RenderMaster) {
+    pub fn draw(&mut self, master: &mut RenderMaster, targeted_block: Option<&RayHit>) {
         for i in 0..self.items.len() {
             let t = &mut self.item_text[i];
             if i == self.held_item as usize {
@@ -206,12 +254,79 @@ impl<'a> Player<'a> {
             ).as_str());
         }
         self.pos_print.set_string(format!(
-            " X: {} Y: {} Z: {} Grounded {}",
+            " X: {} Y: {} Z: {} Grounded {} Health {} Food {} Saturation {:.1}",
             self.base.position.x,
             self.base.position.y,
             self.base.position.z,
-            self.is_on_ground
+            self.is_on_ground,
+            self.health,
+            self.hunger.food_level(),
+            self.hunger.saturation()
         ).as_str());
+        master.draw_text(OverlayText {
+            string: match targeted_block {
+                Some(hit) => format!(
+                    " Target: {} {} {} Face: {} {} {} Distance: {:.2}",
+                    hit.block_position.x,
+                    hit.block_position.y,
+                    hit.block_position.z,
+                    hit.normal.x,
+                    hit.normal.y,
+                    hit.normal.z,
+                    hit.distance
+                ),
+                None => String::from(" Target: None")
+            },
+            position: Vector2f::new(20., 20. * 7. + 100.),
+            character_size: 25,
+            colour: Color::WHITE
+        });
+    }
+
+    /// @brief Shows the air supply bar while the head is under water or the air is refilling.
+    /// @param master
+    pub fn draw_hud(&self, master: &mut RenderMaster) {
+        if self.is_eyes_in_water || self.air < MAX_AIR {
+            master.draw_air_bar(self.air / MAX_AIR);
+        }
+    }
+
+    pub fn is_in_water(&self) -> bool {
+        self.is_in_water
+    }
+
+    /// @brief Whether the camera is submerged, used for under water rendering effects.
+    pub fn is_eyes_in_water(&self) -> bool {
+        self.is_eyes_in_water
+    }
+
+    pub fn air(&self) -> f32 {
+        self.air
+    }
+
+    pub fn health(&self) -> f32 {
+        self.health
+    }
+
+    pub fn hunger(&self) -> &HungerStats {
+        &self.hunger
+    }
+
+    pub fn add_exhaustion(&mut self, amount: f32) {
+        self.hunger.add_exhaustion(amount);
+    }
+
+    /// @brief Eats the held item if it is food and the player is hungry.
+    pub fn eat_held_item(&mut self) {
+        if !self.hunger.needs_food() {
+            return;
+        }
+
+        let stack = &mut self.items[self.held_item as usize];
+        if let Some(food) = stack.material().food {
+            stack.remove();
+            self.hunger.eat(&food);
+        }
     }
 
     pub fn get_held_items(&self) -> &ItemStack {
@@ -222,11 +337,50 @@ impl<'a> Player<'a> {
         &mut self.items[self.held_item as usize]
     }
 
+    fn update_fluid_state(&mut self, world: &mut World) {
+        let is_liquid_at = |world: &mut World, y_offset: f32| {
+            let block = world.get_block(
+                self.base.position.x as i32,
+                (self.base.position.y + y_offset) as i32,
+                self.base.position.z as i32
+            );
+            block.get_data().read().unwrap().block_data().shader_type == BlockShaderType::Liquid
+        };
+
+        self.is_eyes_in_water = is_liquid_at(world, EYE_OFFSET);
+        self.is_in_water = self.is_eyes_in_water || is_liquid_at(world, FEET_OFFSET);
+    }
+
+    fn update_air(&mut self, dt: f32) {
+        if !self.is_eyes_in_water || self.is_flying {
+            self.air = (self.air + AIR_REFILL_RATE * dt).min(MAX_AIR);
+            self.drown_timer = 0.;
+            return;
+        }
+
+        self.air = (self.air - dt).max(0.);
+        if self.air <= 0. {
+            self.drown_timer += dt;
+            while self.drown_timer >= DROWN_DAMAGE_INTERVAL {
+                self.drown_timer -= DROWN_DAMAGE_INTERVAL;
+                self.health = (self.health - DROWN_DAMAGE).max(0.);
+            }
+        }
+    }
+
     fn jump(&mut self) {
-        if !self.is_flying {
+        if self.is_in_water && !self.is_flying {
+            // Swim upwards for as long as jump is held.
+            self.acceleration.y += SPEED * 2.;
+        } else if !self.is_flying {
             if self.is_on_ground {
                 self.is_on_ground = false;
                 self.acceleration.y += SPEED * 50.;
+                self.hunger.add_exhaustion(if self.is_sprinting {
+                    hunger::EXHAUSTION_SPRINT_JUMP
+                } else {
+                    hunger::EXHAUSTION_JUMP
+                });
             }
         } else {
             self.acceleration.y += SPEED * 3.;
@@ -234,9 +388,12 @@ impl<'a> Player<'a> {
     }
 
     fn keyboard_input(&mut self, keyboard: &Keyboard) {
+        self.is_sprinting = false;
         if keyboard.is_key_down(Key::W) {
             let mut s = SPEED;
-            if Key::LControl.is_pressed() {
+            // Sprinting costs food, so a starving player can only walk.
+            if Key::LControl.is_pressed() && (self.is_flying || self.hunger.can_sprint()) {
+                self.is_sprinting = true;
                 s *= 5.;
             } else if Key::RShift.is_pressed() || Key::LShift.is_pressed() {
                 s *= 0.35;
@@ -323,6 +480,13 @@ impl<'a> Default for Player<'a> {
             is_on_ground: false,
             is_flying: false,
             is_sneak: false,
+            is_sprinting: false,
+            is_in_water: false,
+            is_eyes_in_water: false,
+            health: hunger::MAX_HEALTH,
+            hunger: HungerStats::new(),
+            air: MAX_AIR,
+            drown_timer: 0.,
             items: Vec::new(),
             item_text: Vec::new(),
             pos_print: Text::default(),
@@ -330,6 +494,7 @@ impl<'a> Default for Player<'a> {
             item_down: ToggleKey::new(Key::Down),
             item_up: ToggleKey::new(Key::Up),
             fly_key: ToggleKey::new(Key::F),
+            eat_key: ToggleKey::new(Key::E),
             num1: ToggleKey::new(Key::Num1),
             num2: ToggleKey::new(Key::Num2),
             num3: ToggleKey::new(Key::Num3),
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+extern crate nalgebra_glm as glm;
+
+use gl::types::{GLfloat, GLuint};
+use crate::gl::gl_functions;
+use crate::model::Model;
+use crate::shaders::outline_shader::OutlineShader;
+use crate::shaders::shader::Shader;
+
+/// @brief Renderer that draws the air supply as a bar above the bottom of the screen.
+pub struct AirBarRenderer {
+    quad: Model,
+    shader: OutlineShader
+}
+
+// Size and bottom of the bar, in normalized device coordinates before the aspect correction.
+const BAR_WIDTH: GLfloat = 0.5;
+const BAR_HEIGHT: GLfloat = 0.03;
+const BAR_BOTTOM: GLfloat = -0.85;
+
+impl AirBarRenderer {
+    /// @brief Draws the bar over the frame.
+    /// @param aspect_ratio
+    /// @param fraction Air left, from 0 to 1.
+    pub fn render(&self, aspect_ratio: f32, fraction: f32) {
+        unsafe {
+            gl::Disable(gl::DEPTH_TEST);
+            gl::Enable(gl::BLEND);
+        }
+
+        self.shader.base.base.use_program();
+        self.quad.bind_vao();
+        self.shader.base.load_projection_view_matrix(&glm::identity());
+
+        // The empty part first, then the air left over it from the left edge
+        self.draw_quad(aspect_ratio, 1., &glm::vec4(0., 0., 0., 0.5));
+        self.draw_quad(aspect_ratio, fraction.clamp(0., 1.), &glm::vec4(0.55, 0.8, 1., 0.9));
+
+        unsafe {
+            gl::Enable(gl::DEPTH_TEST);
+        }
+    }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
+
+    fn draw_quad(&self, aspect_ratio: f32, width: f32, colour: &glm::TVec4<f32>) {
+        let mut model_matrix = glm::scale(&glm::identity(), &glm::vec3(1. / aspect_ratio, 1., 1.));
+        model_matrix = glm::translate(&model_matrix, &glm::vec3(-BAR_WIDTH / 2., BAR_BOTTOM, 0.));
+        model_matrix = glm::scale(&model_matrix, &glm::vec3(BAR_WIDTH * width, BAR_HEIGHT, 1.));
+        self.shader.base.load_model_matrix(&model_matrix);
+        self.shader.load_colour(colour);
+
+        gl_functions::draw_elements(self.quad.get_indices_count());
+    }
+}
+
+impl Default for AirBarRenderer {
+    fn default() -> Self {
+        let mut result = Self {
+            quad: Default::default(),
+            shader: Default::default()
+        };
+
+        let vertex_coords = vec![
+            0., 0., 0.,
+            1., 0., 0.,
+            1., 1., 0.,
+            0., 1., 0.
+        ];
+        let indices: Vec<GLuint> = vec![0, 1, 2, 2, 3, 0];
+
+        result.quad.gen_vao();
+        result.quad.add_vbo(3, &vertex_coords);
+        result.quad.add_ebo(&indices);
+
+        result
+    }
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use gl::types::{GLfloat, GLuint};
+use nalgebra_glm::IVec3;
+use crate::camera::Camera;
+use crate::gl::gl_functions;
+use crate::model::Model;
+use crate::shaders::outline_shader::OutlineShader;
+use crate::shaders::shader::Shader;
+
+/// @brief Renderer that draws a wireframe box around the block the player is looking at.
+pub struct BlockOutlineRenderer {
+    cube: Model,
+    shader: OutlineShader,
+
+    target: Option<IVec3>
+}
+
+// Grows the outline slightly so that it is not hidden by the faces of the block itself.
+const OUTLINE_PADDING: GLfloat = 0.002;
+
+impl BlockOutlineRenderer {
+    pub fn add(&mut self, block_position: &IVec3) {
+        self.target = Some(*block_position);
+    }
+
+    pub fn render(&mut self, camera: &Camera) {
+        let target = match self.target.take() {
+            Some(target) => target,
+            None => return
+        };
+
+        unsafe {
+            gl::Enable(gl::DEPTH_TEST);
+            gl::Disable(gl::BLEND);
+        }
+
+        self.shader.base.base.use_program();
+        self.cube.bind_vao();
+
+        let model_matrix = glm::translate(
+            &glm::identity(),
+            &glm::vec3(target.x as f32, target.y as f32, target.z as f32)
+        );
+        self.shader.base.load_projection_view_matrix(&camera.get_projection_view_matrix());
+        self.shader.base.load_model_matrix(&model_matrix);
+        self.shader.load_colour(&glm::vec4(0., 0., 0., 1.));
+
+        gl_functions::draw_line_elements(self.cube.get_indices_count());
+    }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
+}
+
+impl Default for BlockOutlineRenderer {
+    fn default() -> Self {
+        let mut result = Self {
+            cube: Default::default(),
+            shader: Default::default(),
+            target: None
+        };
+
+        const MIN: GLfloat = -OUTLINE_PADDING;
+        const MAX: GLfloat = 1. + OUTLINE_PADDING;
+        let vertex_coords = vec![
+            MIN, MIN, MIN,
+            MAX, MIN, MIN,
+            MAX, MIN, MAX,
+            MIN, MIN, MAX,
+
+            MIN, MAX, MIN,
+            MAX, MAX, MIN,
+            MAX, MAX, MAX,
+            MIN, MAX, MAX
+        ];
+
+        let indices: Vec<GLuint> = vec![
+            // Bottom
+            0, 1, 1, 2, 2, 3, 3, 0,
+            // Top
+            4, 5, 5, 6, 6, 7, 7, 4,
+            // Sides
+            0, 4, 1, 5, 2, 6, 3, 7
+        ];
+
+        result.cube.gen_vao();
+        result.cube.add_vbo(3, &vertex_coords);
+        result.cube.add_ebo(&indices);
+
+        result
+    }
+}
\ No newline at end of file
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sfml::graphics::Image;
+
+pub const SCREENSHOT_DIRECTORY: &str = "Screenshots";
+
+/// Size of each square face of a panorama.
+pub const PANORAMA_SIZE: u32 = 1024;
+
+/// Camera rotations (pitch, yaw) of the six panorama faces, in the order used for
+/// `panorama_0.png` to `panorama_5.png`: front, right, back, left, up and down.
+pub const PANORAMA_ROTATIONS: [[f32; 2]; 6] = [
+    [0., 0.],
+    [0., 90.],
+    [0., 180.],
+    [0., 270.],
+    [-90., 0.],
+    [90., 0.]
+];
+
+/// @brief Reads the colour of the currently bound framebuffer.
+/// @param width
+/// @param height
+/// @return RGBA pixels, bottom row first as OpenGL stores them.
+pub fn read_pixels(width: u32, height: u32) -> Vec<u8> {
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+    unsafe {
+        gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+        gl::ReadPixels(
+            0,
+            0,
+            width as _,
+            height as _,
+            gl::RGBA,
+            gl::UNSIGNED_BYTE,
+            pixels.as_mut_ptr() as _
+        );
+    }
+
+    // Nothing behind the window should show through the saved image
+    pixels.iter_mut().skip(3).step_by(4).for_each(|alpha| *alpha = 255);
+    pixels
+}
+
+/// @brief Writes pixels read back with `read_pixels` to a PNG file, creating its directory.
+/// @param file_path
+/// @param width
+/// @param height
+/// @param pixels
+/// @return Whether the file was written.
+pub fn save_png(file_path: &str, width: u32, height: u32, pixels: &[u8]) -> bool {
+    if let Some(directory) = Path::new(file_path).parent() {
+        if !directory.as_os_str().is_empty() && fs::create_dir_all(directory).is_err() {
+            log::error!("Unable to create directory for {}", file_path);
+            return false;
+        }
+    }
+
+    let Some(mut image) = (unsafe { Image::create_from_pixels(width, height, pixels) }) else {
+        log::error!("Unable to create a {}x{} image for {}", width, height, file_path);
+        return false;
+    };
+    image.flip_vertically();
+
+    if !image.save_to_file(file_path) {
+        log::error!("Unable to save {}", file_path);
+        return false;
+    }
+
+    log::info!("Saved {}", file_path);
+    true
+}
+
+/// @brief Free path for a new screenshot, named after the current (UTC) date and time.
+pub fn screenshot_path() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let name = format_timestamp(seconds);
+
+    let mut path = format!("{}/{}.png", SCREENSHOT_DIRECTORY, name);
+    let mut index = 1;
+    while Path::new(&path).exists() {
+        path = format!("{}/{}_{}.png", SCREENSHOT_DIRECTORY, name, index);
+        index += 1;
+    }
+    path
+}
+
+/// @brief Directory holding the six faces of a new panorama.
+pub fn panorama_directory() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    format!("{}/panorama_{}", SCREENSHOT_DIRECTORY, format_timestamp(seconds))
+}
+
+pub fn panorama_rotation(face: usize) -> glm::TVec3<f32> {
+    let [pitch, yaw] = PANORAMA_ROTATIONS[face];
+    glm::vec3(pitch, yaw, 0.)
+}
+
+/// @brief Formats seconds since the Unix epoch as "YYYY-MM-DD_HH.MM.SS".
+fn format_timestamp(seconds: u64) -> String {
+    let days = (seconds / 86400) as i64;
+    let time = seconds % 86400;
+
+    // Civil date from days since 1970-01-01 (proleptic Gregorian calendar)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}_{:02}.{:02}.{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        (time / 60) % 60,
+        time % 60
+    )
+}
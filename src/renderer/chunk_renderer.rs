@@ -14,27 +14,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::application;
 use crate::camera::Camera;
-use crate::gl::gl_functions;
-use crate::renderer::render_info::RenderInfo;
+use crate::renderer::fog::Fog;
 use crate::shaders::chunk_shader::ChunkShader;
+use crate::shaders::shader::Shader;
 use crate::world::block::block_database::BlockDatabase;
+use crate::world::chunk::chunk_buffer_arena::{ArenaRange, ChunkBufferArena};
 use crate::world::chunk::chunk_mesh::ChunkMesh;
 
 /// @brief Block chunk renderer that helps display block data.
 #[derive(Default)]
 pub struct ChunkRenderer {
-    chunks: Vec<RenderInfo>,
+    chunks: Vec<ArenaRange>,
 
     shader: ChunkShader
 }
 
 impl ChunkRenderer {
     pub fn add(&mut self, mesh: &ChunkMesh) {
-        self.chunks.push(*mesh.get_model().get_render_info());
+        if let Some(range) = mesh.get_range() {
+            self.chunks.push(*range);
+        }
     }
 
-    pub fn render(&mut self, camera: &Camera) {
+    pub fn render(&mut self, camera: &Camera, daylight: f32, fog: &Fog) {
         if self.chunks.is_empty() {
             return;
         }
@@ -45,15 +49,22 @@ impl ChunkRenderer {
         }
 
         self.shader.base.base.use_program();
-        BlockDatabase::get().texture_atlas.bind_texture();
+        BlockDatabase::get().bind_block_textures();
 
         self.shader.base.load_projection_view_matrix(&camera.get_projection_view_matrix());
-
-        for mesh in self.chunks.iter() {
-            gl_functions::bind_vao(mesh.vao);
-            gl_functions::draw_elements(mesh.indices_count as _);
+        self.shader.load_daylight(daylight);
+        self.shader.load_camera_position(&camera.position);
+        self.shader.load_fog(fog);
+        unsafe {
+            self.shader.load_animation_time(application::TIME_ELAPSED);
         }
 
+        ChunkBufferArena::draw(&self.chunks);
+
         self.chunks.clear();
     }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use gl::types::{GLfloat, GLuint};
+use crate::gl::gl_functions;
+use crate::model::Model;
+use crate::shaders::outline_shader::OutlineShader;
+use crate::shaders::shader::Shader;
+
+/// @brief Renderer that draws the crosshair in the middle of the screen.
+pub struct CrosshairRenderer {
+    cross: Model,
+    shader: OutlineShader
+}
+
+// Half the length of a crosshair line, in normalized device coordinates.
+const CROSSHAIR_SIZE: GLfloat = 0.025;
+
+impl CrosshairRenderer {
+    pub fn render(&self, aspect_ratio: f32) {
+        unsafe {
+            gl::Disable(gl::DEPTH_TEST);
+            gl::Enable(gl::BLEND);
+        }
+
+        self.shader.base.base.use_program();
+        self.cross.bind_vao();
+
+        // Drawn straight in screen space, squashed horizontally to keep both lines equally long.
+        let model_matrix = glm::scale(&glm::identity(), &glm::vec3(1. / aspect_ratio, 1., 1.));
+        self.shader.base.load_projection_view_matrix(&glm::identity());
+        self.shader.base.load_model_matrix(&model_matrix);
+        self.shader.load_colour(&glm::vec4(1., 1., 1., 0.8));
+
+        gl_functions::draw_line_elements(self.cross.get_indices_count());
+
+        unsafe {
+            gl::Enable(gl::DEPTH_TEST);
+        }
+    }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
+}
+
+impl Default for CrosshairRenderer {
+    fn default() -> Self {
+        let mut result = Self {
+            cross: Default::default(),
+            shader: Default::default()
+        };
+
+        let vertex_coords = vec![
+            -CROSSHAIR_SIZE, 0., 0.,
+            CROSSHAIR_SIZE, 0., 0.,
+            0., -CROSSHAIR_SIZE, 0.,
+            0., CROSSHAIR_SIZE, 0.
+        ];
+        let indices: Vec<GLuint> = vec![0, 1, 2, 3];
+
+        result.cross.gen_vao();
+        result.cross.add_vbo(3, &vertex_coords);
+        result.cross.add_ebo(&indices);
+
+        result
+    }
+}
\ No newline at end of file
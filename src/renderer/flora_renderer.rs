@@ -16,25 +16,28 @@
 
 use crate::application;
 use crate::camera::Camera;
-use crate::gl::gl_functions;
-use crate::renderer::render_info::RenderInfo;
+use crate::renderer::fog::Fog;
 use crate::shaders::flora_shader::FloraShader;
+use crate::shaders::shader::Shader;
+use crate::world::chunk::chunk_buffer_arena::{ArenaRange, ChunkBufferArena};
 use crate::world::chunk::chunk_mesh::ChunkMesh;
 
 /// @brief Renderer handling 'flora' based entities that are not true blocks.
 #[derive(Default)]
 pub struct FloraRenderer {
-    chunks: Vec<RenderInfo>,
+    chunks: Vec<ArenaRange>,
 
     shader: FloraShader
 }
 
 impl FloraRenderer {
     pub fn add(&mut self, mesh: &ChunkMesh) {
-        self.chunks.push(*mesh.get_model().get_render_info());
+        if let Some(range) = mesh.get_range() {
+            self.chunks.push(*range);
+        }
     }
 
-    pub fn render(&mut self, camera: &Camera) {
+    pub fn render(&mut self, camera: &Camera, daylight: f32, fog: &Fog) {
         if self.chunks.is_empty() {
             return;
         }
@@ -43,18 +46,23 @@ impl FloraRenderer {
             gl::Disable(gl::BLEND);
             gl::Disable(gl::CULL_FACE);
         }
-        self.shader.base.base.use_program();
+        self.shader.base.base.base.use_program();
 
-        self.shader.base.load_projection_view_matrix(&camera.get_projection_view_matrix());
+        self.shader.base.base.load_projection_view_matrix(&camera.get_projection_view_matrix());
+        self.shader.base.load_daylight(daylight);
+        self.shader.base.load_camera_position(&camera.position);
+        self.shader.base.load_fog(fog);
         unsafe {
+            self.shader.base.load_animation_time(application::TIME_ELAPSED);
             self.shader.load_time(application::TIME_ELAPSED);
         }
 
-        for mesh in self.chunks.iter() {
-            gl_functions::bind_vao(mesh.vao);
-            gl_functions::draw_elements(mesh.indices_count as _);
-        }
+        ChunkBufferArena::draw(&self.chunks);
 
         self.chunks.clear();
     }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use crate::world::world_constants::CHUNK_SIZE;
+use crate::world::world_time::WorldTime;
+
+/// Fraction of the fog distance that stays completely clear.
+const FOG_START_FACTOR: f32 = 0.6;
+
+const UNDERWATER_FOG_COLOUR: [f32; 3] = [0.05, 0.2, 0.4];
+const UNDERWATER_FOG_START: f32 = 0.;
+const UNDERWATER_FOG_END: f32 = 16.;
+
+/// Height of the view direction above the horizon up to which the fog covers the sky.
+const SKY_FOG_HEIGHT: f32 = 0.15;
+/// Underwater the fog hides the whole sky.
+const UNDERWATER_SKY_FOG_HEIGHT: f32 = 100.;
+
+/// @brief Linear distance fog, blended into the terrain and the sky around the horizon.
+#[derive(Copy, Clone, Debug)]
+pub struct Fog {
+    pub colour: glm::TVec3<f32>,
+    pub start: f32,
+    pub end: f32,
+    pub sky_height: f32
+}
+
+impl Fog {
+    /// @brief Fog matching the current sky colour that fully hides the terrain right before
+    /// chunks are unloaded, or a dense fog while the camera is underwater.
+    /// @param world_time
+    /// @param render_distance In chunks.
+    /// @param is_underwater
+    pub fn new(world_time: &WorldTime, render_distance: i32, is_underwater: bool) -> Self {
+        if is_underwater {
+            return Self {
+                colour: glm::make_vec3(&UNDERWATER_FOG_COLOUR) * world_time.daylight(),
+                start: UNDERWATER_FOG_START,
+                end: UNDERWATER_FOG_END,
+                sky_height: UNDERWATER_SKY_FOG_HEIGHT
+            };
+        }
+
+        // Chunks are kept up to `render_distance` away from the camera's chunk, so the camera
+        // can be up to a chunk closer to the edge than that.
+        let end = ((render_distance - 1).max(1) * CHUNK_SIZE as i32) as f32;
+        Self {
+            colour: world_time.horizon_colour(),
+            start: end * FOG_START_FACTOR,
+            end,
+            sky_height: SKY_FOG_HEIGHT
+        }
+    }
+}
@@ -16,7 +16,17 @@
 
 pub mod render_master;
 pub mod render_info;
+pub mod fog;
+pub mod post_processor;
+pub mod capture;
 pub mod chunk_renderer;
 pub mod flora_renderer;
 pub mod skybox_renderer;
-pub mod water_renderer;
\ No newline at end of file
+pub mod water_renderer;
+pub mod translucent_renderer;
+pub mod block_outline_renderer;
+pub mod crosshair_renderer;
+pub mod texture_preview_renderer;
+pub mod text_overlay_renderer;
+
+pub mod air_bar_renderer;
\ No newline at end of file
@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use gl::types::GLuint;
+use crate::config::Config;
+use crate::gl::framebuffer::Framebuffer;
+use crate::gl::gl_functions;
+use crate::shaders::post_effect_shader::PostEffectShader;
+use crate::shaders::shader::Shader;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PostEffect {
+    GammaBrightness,
+    Underwater,
+    Vignette,
+    Fxaa,
+    ColourBlind,
+    /// Plain copy onto the window, used when no enabled effect applies this frame
+    Copy
+}
+
+/// @brief Renders the scene into an off-screen HDR framebuffer, then runs it through a chain
+/// of full screen effects, the last of which draws on the window.
+pub struct PostProcessor {
+    effects: Vec<(PostEffect, PostEffectShader)>,
+    copy: (PostEffect, PostEffectShader),
+
+    // The scene is drawn into the first one, effects then ping-pong between both
+    targets: Vec<Framebuffer>,
+    vao: GLuint,
+
+    gamma: f32,
+    brightness: f32,
+    colour_blind_mode: i32
+}
+
+impl PostEffect {
+    fn fragment_file(&self) -> &'static str {
+        match self {
+            PostEffect::GammaBrightness => "PostGamma",
+            PostEffect::Underwater => "PostUnderwater",
+            PostEffect::Vignette => "PostVignette",
+            PostEffect::Fxaa => "PostFxaa",
+            PostEffect::ColourBlind => "PostColourBlind",
+            PostEffect::Copy => "Post"
+        }
+    }
+}
+
+impl PostProcessor {
+    pub fn new(config: &Config) -> Self {
+        // Anti-aliasing and colour filters work on the final, display ready colours
+        let enabled = [
+            (PostEffect::GammaBrightness, config.gamma_correction),
+            (PostEffect::Underwater, config.underwater_effect),
+            (PostEffect::Vignette, config.vignette),
+            (PostEffect::Fxaa, config.fxaa),
+            (PostEffect::ColourBlind, config.colour_blind_mode != 0)
+        ];
+
+        let mut vao = 0;
+        unsafe {
+            gl::GenVertexArrays(1, &mut vao);
+        }
+
+        Self {
+            effects: enabled.iter()
+                .filter(|(_, is_enabled)| *is_enabled)
+                .map(|(effect, _)| (*effect, PostEffectShader::new(effect.fragment_file())))
+                .collect(),
+            copy: (PostEffect::Copy, PostEffectShader::new(PostEffect::Copy.fragment_file())),
+            targets: Vec::new(),
+            vao,
+            gamma: config.gamma,
+            brightness: config.brightness,
+            colour_blind_mode: config.colour_blind_mode
+        }
+    }
+
+    /// @brief Redirects rendering to the scene framebuffer, (re)creating the framebuffers
+    /// whenever the window size changed.
+    /// @param width
+    /// @param height
+    pub fn begin(&mut self, width: u32, height: u32) {
+        let is_outdated = self.targets.first()
+            .map_or(true, |target| target.width() != width || target.height() != height);
+        if is_outdated {
+            self.targets = vec![Framebuffer::new(width, height), Framebuffer::new(width, height)];
+        }
+
+        self.targets[0].bind();
+    }
+
+    /// @brief Applies the effect chain to the scene and draws the result on the window,
+    /// or into `output` when one is given.
+    /// @param is_underwater
+    /// @param time Seconds since start, used to animate effects.
+    /// @param output
+    pub fn finish(&mut self, is_underwater: bool, time: f32, output: Option<&Framebuffer>) {
+        let mut passes: Vec<&(PostEffect, PostEffectShader)> = self.effects.iter()
+            .filter(|(effect, _)| *effect != PostEffect::Underwater || is_underwater)
+            .collect();
+        if passes.is_empty() {
+            passes.push(&self.copy);
+        }
+
+        unsafe {
+            gl::Disable(gl::DEPTH_TEST);
+            gl::Disable(gl::BLEND);
+            gl::Disable(gl::CULL_FACE);
+            gl::ActiveTexture(gl::TEXTURE0);
+        }
+        gl_functions::bind_vao(self.vao);
+
+        let resolution = glm::vec2(self.targets[0].width() as f32, self.targets[0].height() as f32);
+        let mut source = 0;
+        for (i, (effect, shader)) in passes.iter().enumerate() {
+            if i == passes.len() - 1 {
+                match output {
+                    Some(output) => output.bind(),
+                    None => Framebuffer::bind_default()
+                }
+            } else {
+                self.targets[1 - source].bind();
+            }
+
+            shader.base.use_program();
+            shader.load_resolution(&resolution);
+            shader.load_time(time);
+            if *effect == PostEffect::GammaBrightness {
+                shader.load_gamma(self.gamma);
+                shader.load_brightness(self.brightness);
+            } else if *effect == PostEffect::ColourBlind {
+                shader.load_mode(self.colour_blind_mode);
+            }
+
+            self.targets[source].bind_colour_texture();
+            gl_functions::draw_fullscreen_triangle();
+            source = 1 - source;
+        }
+
+        unsafe {
+            gl::Enable(gl::DEPTH_TEST);
+        }
+    }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.effects.iter_mut()
+            .chain(std::iter::once(&mut self.copy))
+            .fold(true, |is_success, (_, shader)| shader.reload() && is_success)
+    }
+}
+
+impl Drop for PostProcessor {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
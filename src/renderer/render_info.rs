@@ -14,6 +14,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+extern crate nalgebra_glm as glm;
+
+use std::cmp::Ordering;
 use gl::types::GLuint;
 
 #[derive(Copy, Clone, Default)]
@@ -27,4 +30,18 @@ impl RenderInfo {
         self.vao = 0;
         self.indices_count = 0;
     }
+}
+
+/// @brief Orders meshes, given with their centre, from the furthest to the closest to the camera.
+/// @param meshes
+/// @param camera_position
+pub fn sort_back_to_front<T>(
+    meshes: &mut [(T, glm::TVec3<f32>)],
+    camera_position: &glm::TVec3<f32>
+) {
+    meshes.sort_by(|(_, a), (_, b)| {
+        glm::distance2(b, camera_position)
+            .partial_cmp(&glm::distance2(a, camera_position))
+            .unwrap_or(Ordering::Equal)
+    });
 }
\ No newline at end of file
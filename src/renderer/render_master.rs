@@ -14,72 +14,276 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use gl::types::GLuint;
+use nalgebra_glm::{IVec3, TVec3};
 use sfml::window::Window;
+use crate::application;
 use crate::camera::Camera;
+use crate::config::Config;
+use crate::gl::framebuffer::Framebuffer;
+use crate::renderer::air_bar_renderer::AirBarRenderer;
+use crate::renderer::block_outline_renderer::BlockOutlineRenderer;
+use crate::renderer::capture;
 use crate::renderer::chunk_renderer::ChunkRenderer;
+use crate::renderer::crosshair_renderer::CrosshairRenderer;
 use crate::renderer::flora_renderer::FloraRenderer;
+use crate::renderer::fog::Fog;
+use crate::renderer::post_processor::PostProcessor;
 use crate::renderer::skybox_renderer::SkyboxRenderer;
+use crate::renderer::text_overlay_renderer::{OverlayText, TextOverlayRenderer};
+use crate::renderer::texture_preview_renderer::TexturePreviewRenderer;
+use crate::renderer::translucent_renderer::TranslucentRenderer;
 use crate::renderer::water_renderer::WaterRenderer;
+use crate::texture::texture_atlas::TextureAtlas;
+use crate::world::chunk::chunk_buffer_arena::ChunkBufferArena;
+use crate::world::chunk::chunk_lod::ChunkLod;
+use crate::world::chunk::chunk_mesh::ChunkMeshCollection;
 use crate::world::chunk::chunk_section::ChunkSection;
+use crate::world::world_time::WorldTime;
 
 /// @brief Master rendering class that handles the sum of drawn in-game objects.
-#[derive(Default)]
 pub struct RenderMaster {
     // Chunks
     chunk_renderer: ChunkRenderer,
     water_renderer: WaterRenderer,
     flora_renderer: FloraRenderer,
+    translucent_renderer: TranslucentRenderer,
 
     // Detail
     skybox_renderer: SkyboxRenderer,
+    block_outline_renderer: BlockOutlineRenderer,
+    crosshair_renderer: CrosshairRenderer,
+    air_bar_renderer: AirBarRenderer,
+    texture_preview_renderer: TexturePreviewRenderer,
+    text_overlay_renderer: TextOverlayRenderer,
+
+    post_processor: PostProcessor,
+
+    world_time: WorldTime,
+    is_underwater: bool,
     
-    draw_box: bool
+    draw_box: bool,
+    draw_crosshair: bool,
+    take_screenshot: bool,
+    preview_texture: Option<GLuint>,
+    air_fraction: Option<f32>,
+    overlay_texts: Vec<OverlayText>
 }
 
 impl RenderMaster {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            chunk_renderer: Default::default(),
+            water_renderer: Default::default(),
+            flora_renderer: Default::default(),
+            translucent_renderer: Default::default(),
+            skybox_renderer: Default::default(),
+            block_outline_renderer: Default::default(),
+            crosshair_renderer: Default::default(),
+            air_bar_renderer: Default::default(),
+            texture_preview_renderer: Default::default(),
+            text_overlay_renderer: Default::default(),
+            post_processor: PostProcessor::new(config),
+            world_time: Default::default(),
+            is_underwater: false,
+            draw_box: false,
+            draw_crosshair: false,
+            take_screenshot: false,
+            preview_texture: None,
+            air_fraction: None,
+            overlay_texts: Vec::new()
+        }
+    }
+
     pub fn draw_chunk(&mut self, chunk: &ChunkSection) {
-        let solid_mesh = &chunk.get_meshes().solid_mesh;
-        let water_mesh = &chunk.get_meshes().water_mesh;
-        let flora_mesh = &chunk.get_meshes().flora_mesh;
+        self.draw_meshes(chunk.get_meshes(), &chunk.aabb.centre());
+    }
 
-        if solid_mesh.faces > 0 {
-            self.chunk_renderer.add(solid_mesh);
+    /// @brief Draws the coarse mesh of a distant chunk column.
+    /// @param lod
+    pub fn draw_lod(&mut self, lod: &ChunkLod) {
+        self.draw_meshes(lod.get_meshes(), &lod.aabb.centre());
+    }
+
+    pub fn draw_sky(&mut self) {
+        self.draw_box = true;
+    }
+
+    pub fn set_world_time(&mut self, world_time: &WorldTime) {
+        self.world_time = *world_time;
+    }
+
+    /// @brief Switches to the dense underwater fog while the camera is submerged.
+    /// @param is_underwater
+    pub fn set_underwater(&mut self, is_underwater: bool) {
+        self.is_underwater = is_underwater;
+    }
+
+    pub fn draw_block_outline(&mut self, block_position: &IVec3) {
+        self.block_outline_renderer.add(block_position);
+    }
+
+    pub fn draw_crosshair(&mut self) {
+        self.draw_crosshair = true;
+    }
+
+    /// @brief Shows the air supply bar over the next frame.
+    /// @param fraction Air left, from 0 to 1.
+    pub fn draw_air_bar(&mut self, fraction: f32) {
+        self.air_fraction = Some(fraction);
+    }
+
+    /// @brief Shows the whole atlas over the next frame.
+    /// @param texture
+    pub fn draw_texture_preview(&mut self, texture: &TextureAtlas) {
+        self.preview_texture = Some(texture.id());
+    }
+
+    /// @brief Shows a line of text over the next frame.
+    /// @param text
+    pub fn draw_text(&mut self, text: OverlayText) {
+        self.overlay_texts.push(text);
+    }
+
+    /// @brief Recompiles every shader from the shader directory.
+    /// Shaders that fail to compile keep their previous program, with the compile log in the log.
+    /// @return Whether all of them compiled.
+    pub fn reload_shaders(&mut self) -> bool {
+        let results = [
+            self.chunk_renderer.reload_shaders(),
+            self.water_renderer.reload_shaders(),
+            self.flora_renderer.reload_shaders(),
+            self.translucent_renderer.reload_shaders(),
+            self.skybox_renderer.reload_shaders(),
+            self.block_outline_renderer.reload_shaders(),
+            self.crosshair_renderer.reload_shaders(),
+            self.air_bar_renderer.reload_shaders(),
+            self.texture_preview_renderer.reload_shaders(),
+            self.text_overlay_renderer.reload_shaders(),
+            self.post_processor.reload_shaders()
+        ];
+        results.iter().all(|is_success| *is_success)
+    }
+
+    /// @brief Saves the next displayed frame, crosshair included, as a timestamped PNG.
+    pub fn request_screenshot(&mut self) {
+        self.take_screenshot = true;
+    }
+
+    pub fn finish_render(&mut self, window: &mut Window, camera: &Camera) {
+        let size = window.size();
+        self.render_scene(camera, size.x, size.y, None);
+
+        if let Some(texture) = self.preview_texture.take() {
+            self.texture_preview_renderer.render(texture, size.x, size.y);
         }
 
-        if water_mesh.faces > 0 {
-            self.water_renderer.add(water_mesh);
+        if self.draw_crosshair {
+            self.crosshair_renderer.render(size.x as f32 / size.y as f32);
+            self.draw_crosshair = false;
         }
 
-        if flora_mesh.faces > 0 {
-            self.flora_renderer.add(flora_mesh);
+        if let Some(fraction) = self.air_fraction.take() {
+            self.air_bar_renderer.render(size.x as f32 / size.y as f32, fraction);
         }
+
+        if !self.overlay_texts.is_empty() {
+            self.text_overlay_renderer.render(window, &self.overlay_texts);
+            self.overlay_texts.clear();
+        }
+
+        if self.take_screenshot {
+            let pixels = capture::read_pixels(size.x, size.y);
+            capture::save_png(&capture::screenshot_path(), size.x, size.y, &pixels);
+            self.take_screenshot = false;
+        }
+
+        window.display();
     }
 
-    pub fn draw_sky(&mut self) {
-        self.draw_box = true;
+    /// @brief Renders everything queued so far from the given camera into an image file,
+    /// leaving the window untouched.
+    /// @param camera
+    /// @param width
+    /// @param height
+    /// @param file_path
+    /// @return Whether the file was written.
+    pub fn capture(&mut self, camera: &Camera, width: u32, height: u32, file_path: &str) -> bool {
+        let target = Framebuffer::new(width, height);
+        self.render_scene(camera, width, height, Some(&target));
+
+        target.bind();
+        let pixels = capture::read_pixels(width, height);
+        Framebuffer::bind_default();
+
+        capture::save_png(file_path, width, height, &pixels)
     }
 
-    pub fn finish_render(&mut self, window: &mut Window, camera: &Camera) {
+    fn render_scene(&mut self, camera: &Camera, width: u32, height: u32, output: Option<&Framebuffer>) {
+        // Distant columns are drawn with coarse meshes up to the LOD distance
+        let config = camera.get_config();
+        let fog = Fog::new(
+            &self.world_time,
+            config.render_distance.max(config.lod_distance),
+            self.is_underwater
+        );
+
+        self.post_processor.begin(width, height);
+        ChunkBufferArena::begin_frame();
+
         unsafe {
-            gl::ClearColor(0.0, 0.0, 0.0, 1.0);
+            gl::Viewport(0, 0, width as _, height as _);
+            gl::ClearColor(fog.colour.x, fog.colour.y, fog.colour.z, 1.0);
             gl::Clear(gl::DEPTH_BUFFER_BIT | gl::COLOR_BUFFER_BIT);
 
             gl::Enable(gl::DEPTH_TEST);
             gl::Enable(gl::CULL_FACE);
         }
         
-        self.chunk_renderer.render(camera);
-        self.water_renderer.render(camera);
-        self.flora_renderer.render(camera);
+        let daylight = self.world_time.daylight();
+        self.chunk_renderer.render(camera, daylight, &fog);
+        self.flora_renderer.render(camera, daylight, &fog);
 
         if self.draw_box {
             unsafe {
                 gl::Disable(gl::CULL_FACE);
             }
-            self.skybox_renderer.render(camera);
+            self.skybox_renderer.render(camera, &self.world_time, &fog);
             self.draw_box = false;
         }
 
-        window.display();
+        // Blended faces go last, so that the sky and terrain behind them are already drawn
+        self.water_renderer.render(camera, daylight, &fog);
+        self.translucent_renderer.render(camera, daylight, &fog);
+
+        self.block_outline_renderer.render(camera);
+
+        // Everything drawn after this goes straight to the window, untouched by the effects
+        unsafe {
+            self.post_processor.finish(self.is_underwater, application::TIME_ELAPSED, output);
+        }
+    }
+
+    fn draw_meshes(&mut self, meshes: &ChunkMeshCollection, centre: &TVec3<f32>) {
+        let solid_mesh = &meshes.solid_mesh;
+        let water_mesh = &meshes.water_mesh;
+        let flora_mesh = &meshes.flora_mesh;
+        let translucent_mesh = &meshes.translucent_mesh;
+
+        if solid_mesh.faces > 0 {
+            self.chunk_renderer.add(solid_mesh);
+        }
+
+        if water_mesh.faces > 0 {
+            self.water_renderer.add(water_mesh, centre);
+        }
+
+        if flora_mesh.faces > 0 {
+            self.flora_renderer.add(flora_mesh);
+        }
+
+        if translucent_mesh.faces > 0 {
+            self.translucent_renderer.add(translucent_mesh, centre);
+        }
     }
 }
\ No newline at end of file
@@ -18,10 +18,15 @@ use gl::types::{GLfloat, GLuint};
 use crate::camera::Camera;
 use crate::gl::gl_functions;
 use crate::model::Model;
+use crate::renderer::fog::Fog;
 use crate::shaders::skybox_shader::SkyboxShader;
+use crate::shaders::shader::Shader;
 use crate::texture::cube_texture::CubeTexture;
+use crate::world::world_time::WorldTime;
 
 /// @brief Renderer that specifically draws the skybox and entities outside player reach.
+///
+/// The sky colour, sun, moon and stars are generated in the shader from the world time.
 pub struct SkyboxRenderer {
     sky_cube: Model,
     shader: SkyboxShader,
@@ -29,16 +34,22 @@ pub struct SkyboxRenderer {
 }
 
 impl SkyboxRenderer {
-    pub fn render(&self, camera: &Camera) {
+    pub fn render(&self, camera: &Camera, world_time: &WorldTime, fog: &Fog) {
         self.shader.base.use_program();
         self.sky_cube.bind_vao();
         self.cube_texture.bind_texture();
         
         self.shader.load_view_matrix(&camera.get_view_matrix());
         self.shader.load_projection_matrix(&camera.get_proj_matrix());
+        self.shader.load_world_time(world_time);
+        self.shader.load_fog(fog);
         
         gl_functions::draw_elements(self.sky_cube.get_indices_count());
     }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
 }
 
 impl Default for SkyboxRenderer {
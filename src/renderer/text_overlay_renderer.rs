@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gl::types::GLuint;
+use sfml::graphics::{Color, Font, RenderTarget, RenderTexture, Text, Transformable};
+use sfml::SfBox;
+use sfml::system::Vector2f;
+use sfml::window::Window;
+use crate::gl::gl_functions;
+use crate::shaders::post_effect_shader::PostEffectShader;
+use crate::shaders::shader::Shader;
+
+const FONT_FILE: &str = "Res/Fonts/rs.ttf";
+
+/// @brief A line of text to show over the next frame.
+pub struct OverlayText {
+    pub string: String,
+    /// Top left corner, in pixels from the top left of the window
+    pub position: Vector2f,
+    pub character_size: u32,
+    pub colour: Color
+}
+
+/// @brief Renderer that shows text over the finished frame.
+///
+/// The window is a bare OpenGL window SFML cannot draw into, so the text is drawn into an
+/// SFML render texture first, which then covers the screen like a post effect.
+pub struct TextOverlayRenderer {
+    shader: PostEffectShader,
+    vao: GLuint,
+    font: Option<SfBox<Font>>,
+    target: Option<RenderTexture>
+}
+
+impl TextOverlayRenderer {
+    /// @brief Draws the given lines over the window's framebuffer.
+    /// @param window Made active again once the text is drawn.
+    /// @param texts
+    pub fn render(&mut self, window: &mut Window, texts: &[OverlayText]) {
+        let Some(font) = self.font.as_ref() else {
+            return;
+        };
+        let size = window.size();
+
+        if self.target.as_ref().map_or(true, |target| target.size() != size) {
+            self.target = RenderTexture::new(size.x, size.y);
+        }
+        let Some(target) = self.target.as_mut() else {
+            log::error!("Unable to create the text overlay at {}x{}", size.x, size.y);
+            window.set_active(true);
+            return;
+        };
+
+        // SFML may draw through the window's context, so keep it away from our vertex array and
+        // let it save and restore the rest of the state around its own drawing
+        gl_functions::bind_vao(0);
+        target.set_active(true);
+        target.push_gl_states();
+        target.clear(Color::TRANSPARENT);
+        for overlay_text in texts {
+            let mut text = Text::new(overlay_text.string.as_str(), font, overlay_text.character_size);
+            text.set_position(overlay_text.position);
+            text.set_fill_color(overlay_text.colour);
+            text.set_outline_color(Color::BLACK);
+            text.set_outline_thickness(2.0);
+            target.draw(&text);
+        }
+        target.display();
+        target.pop_gl_states();
+        target.set_active(false);
+        window.set_active(true);
+
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+            gl::Viewport(0, 0, size.x as i32, size.y as i32);
+            gl::Disable(gl::DEPTH_TEST);
+            gl::Enable(gl::BLEND);
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, target.texture().native_handle());
+        }
+
+        self.shader.base.use_program();
+        gl_functions::bind_vao(self.vao);
+        gl_functions::draw_fullscreen_triangle();
+
+        unsafe {
+            gl::Enable(gl::DEPTH_TEST);
+        }
+    }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
+}
+
+impl Default for TextOverlayRenderer {
+    fn default() -> Self {
+        let mut vao = 0;
+        unsafe {
+            gl::GenVertexArrays(1, &mut vao);
+        }
+
+        let font = Font::from_file(FONT_FILE);
+        if font.is_none() {
+            log::error!("Unable to load font: {}", FONT_FILE);
+        }
+
+        Self {
+            shader: PostEffectShader::new("TextOverlay"),
+            vao,
+            font,
+            target: None
+        }
+    }
+}
+
+impl Drop for TextOverlayRenderer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gl::types::GLuint;
+use crate::gl::gl_functions;
+use crate::shaders::post_effect_shader::PostEffectShader;
+use crate::shaders::shader::Shader;
+
+/// @brief Renderer that shows a whole texture as a square in the middle of the screen.
+pub struct TexturePreviewRenderer {
+    shader: PostEffectShader,
+    vao: GLuint
+}
+
+// Side of the preview, relative to the smallest side of the window.
+const PREVIEW_SCALE: f32 = 0.8;
+
+impl TexturePreviewRenderer {
+    pub fn render(&self, texture: GLuint, width: u32, height: u32) {
+        let size = (width.min(height) as f32 * PREVIEW_SCALE) as u32;
+
+        unsafe {
+            gl::Disable(gl::DEPTH_TEST);
+            gl::Enable(gl::BLEND);
+
+            // The full screen triangle only covers the preview area
+            gl::Viewport(((width - size) / 2) as _, ((height - size) / 2) as _, size as _, size as _);
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, texture);
+        }
+
+        self.shader.base.use_program();
+        gl_functions::bind_vao(self.vao);
+        gl_functions::draw_fullscreen_triangle();
+
+        unsafe {
+            gl::Viewport(0, 0, width as _, height as _);
+            gl::Enable(gl::DEPTH_TEST);
+        }
+    }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
+}
+
+impl Default for TexturePreviewRenderer {
+    fn default() -> Self {
+        let mut vao = 0;
+        unsafe {
+            gl::GenVertexArrays(1, &mut vao);
+        }
+
+        Self {
+            shader: PostEffectShader::new("TexturePreview"),
+            vao
+        }
+    }
+}
+
+impl Drop for TexturePreviewRenderer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
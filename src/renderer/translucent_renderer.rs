@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use crate::application;
+use crate::camera::Camera;
+use crate::renderer::fog::Fog;
+use crate::renderer::render_info::sort_back_to_front;
+use crate::shaders::chunk_shader::ChunkShader;
+use crate::shaders::shader::Shader;
+use crate::world::block::block_database::BlockDatabase;
+use crate::world::chunk::chunk_buffer_arena::{ArenaRange, ChunkBufferArena};
+use crate::world::chunk::chunk_mesh::ChunkMesh;
+
+/// @brief Renderer for blended chunk faces such as glass, drawn back-to-front without
+/// writing depth so that everything behind them stays visible.
+#[derive(Default)]
+pub struct TranslucentRenderer {
+    chunks: Vec<(ArenaRange, glm::TVec3<f32>)>,
+
+    shader: ChunkShader
+}
+
+impl TranslucentRenderer {
+    pub fn add(&mut self, mesh: &ChunkMesh, centre: &glm::TVec3<f32>) {
+        if let Some(range) = mesh.get_range() {
+            self.chunks.push((*range, *centre));
+        }
+    }
+
+    pub fn render(&mut self, camera: &Camera, daylight: f32, fog: &Fog) {
+        if self.chunks.is_empty() {
+            return;
+        }
+
+        unsafe {
+            gl::Enable(gl::BLEND);
+            gl::Disable(gl::CULL_FACE);
+            gl::DepthMask(gl::FALSE);
+        }
+
+        self.shader.base.base.use_program();
+        BlockDatabase::get().bind_block_textures();
+
+        self.shader.base.load_projection_view_matrix(&camera.get_projection_view_matrix());
+        self.shader.load_daylight(daylight);
+        self.shader.load_camera_position(&camera.position);
+        self.shader.load_fog(fog);
+        unsafe {
+            self.shader.load_animation_time(application::TIME_ELAPSED);
+        }
+
+        sort_back_to_front(&mut self.chunks, &camera.position);
+        let ranges: Vec<ArenaRange> = self.chunks.iter().map(|(range, _)| *range).collect();
+        ChunkBufferArena::draw(&ranges);
+
+        unsafe {
+            gl::DepthMask(gl::TRUE);
+        }
+
+        self.chunks.clear();
+    }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
+}
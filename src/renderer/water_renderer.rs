@@ -14,27 +14,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+extern crate nalgebra_glm as glm;
+
 use crate::application;
 use crate::camera::Camera;
-use crate::gl::gl_functions;
-use crate::renderer::render_info::RenderInfo;
+use crate::renderer::fog::Fog;
+use crate::renderer::render_info::sort_back_to_front;
 use crate::shaders::water_shader::WaterShader;
+use crate::shaders::shader::Shader;
+use crate::world::chunk::chunk_buffer_arena::{ArenaRange, ChunkBufferArena};
 use crate::world::chunk::chunk_mesh::ChunkMesh;
 
 /// @brief Renderer specifically targeting water and handling shader behaviors.
 #[derive(Default)]
 pub struct WaterRenderer {
-    chunks: Vec<RenderInfo>,
+    chunks: Vec<(ArenaRange, glm::TVec3<f32>)>,
 
     shader: WaterShader
 }
 
 impl WaterRenderer {
-    pub fn add(&mut self, mesh: &ChunkMesh) {
-        self.chunks.push(*mesh.get_model().get_render_info());
+    pub fn add(&mut self, mesh: &ChunkMesh, centre: &glm::TVec3<f32>) {
+        if let Some(range) = mesh.get_range() {
+            self.chunks.push((*range, *centre));
+        }
     }
 
-    pub fn render(&mut self, camera: &Camera) {
+    pub fn render(&mut self, camera: &Camera, daylight: f32, fog: &Fog) {
         if self.chunks.is_empty() {
             return;
         }
@@ -42,19 +48,31 @@ impl WaterRenderer {
         unsafe {
             gl::Enable(gl::BLEND);
             gl::Disable(gl::CULL_FACE);
+            gl::DepthMask(gl::FALSE);
         }
-        self.shader.base.base.use_program();
+        self.shader.base.base.base.use_program();
 
-        self.shader.base.load_projection_view_matrix(&camera.get_projection_view_matrix());
+        self.shader.base.base.load_projection_view_matrix(&camera.get_projection_view_matrix());
+        self.shader.base.load_daylight(daylight);
+        self.shader.base.load_camera_position(&camera.position);
+        self.shader.base.load_fog(fog);
         unsafe {
+            self.shader.base.load_animation_time(application::TIME_ELAPSED);
             self.shader.load_time(application::TIME_ELAPSED);
         }
 
-        for mesh in self.chunks.iter() {
-            gl_functions::bind_vao(mesh.vao);
-            gl_functions::draw_elements(mesh.indices_count as _);
+        sort_back_to_front(&mut self.chunks, &camera.position);
+        let ranges: Vec<ArenaRange> = self.chunks.iter().map(|(range, _)| *range).collect();
+        ChunkBufferArena::draw(&ranges);
+
+        unsafe {
+            gl::DepthMask(gl::TRUE);
         }
 
         self.chunks.clear();
     }
+
+    pub fn reload_shaders(&mut self) -> bool {
+        self.shader.reload()
+    }
 }
\ No newline at end of file
@@ -16,7 +16,6 @@
 
 extern crate nalgebra_glm as glm;
 
-use std::ffi::CString;
 use gl::types::GLint;
 use crate::shaders::shader::{Shader, ShaderBase};
 
@@ -58,15 +57,15 @@ impl Default for BasicShader {
 impl Shader for BasicShader {
     fn get_uniforms(&mut self) {
         self.base.use_program();
-        unsafe {
-            let c_string = CString::new("projViewMatrix").unwrap();
-            self.location_projection_view_matrix = gl::GetUniformLocation(
-                self.base.id, c_string.as_ptr()
-            );
-            let c_string = CString::new("modelMatrix").unwrap();
-            self.location_model_matrix = gl::GetUniformLocation(
-                self.base.id, c_string.as_ptr()
-            );
+        self.location_projection_view_matrix = self.base.get_uniform_location("projViewMatrix");
+        self.location_model_matrix = self.base.get_optional_uniform_location("modelMatrix");
+    }
+
+    fn reload(&mut self) -> bool {
+        if !self.base.reload() {
+            return false;
         }
+        self.get_uniforms();
+        true
     }
 }
\ No newline at end of file
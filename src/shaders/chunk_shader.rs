@@ -14,25 +14,100 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+extern crate nalgebra_glm as glm;
+
+use gl::types::GLint;
+use crate::renderer::fog::Fog;
 use crate::shaders::basic_shader::BasicShader;
-use crate::shaders::shader::Shader;
+use crate::shaders::shader::{Shader, ShaderBase};
+use crate::texture::texture_animation::TEXTURE_ANIMATIONS_UNIT;
+use crate::texture::texture_atlas::TEXTURE_REGIONS_UNIT;
+use crate::world::chunk::chunk_buffer_arena::SECTION_ORIGINS_UNIT;
 
+/// @brief Shader for chunk meshes, lit by the daylight and faded into the fog.
+///
+/// The water and flora shaders build on top of it with their own vertex stage.
 pub struct ChunkShader {
-    pub base: BasicShader
+    pub base: BasicShader,
+
+    location_daylight: GLint,
+    location_camera_position: GLint,
+    location_fog_colour: GLint,
+    location_fog_start: GLint,
+    location_fog_end: GLint,
+    location_animation_time: GLint
 }
 
-impl Default for ChunkShader {
-    fn default() -> Self {
+impl ChunkShader {
+    pub fn new(vertex_file: &str, fragment_file: &str) -> Self {
         let mut result = Self {
-            base: BasicShader::new("Chunk", "Chunk")
+            base: BasicShader::new(vertex_file, fragment_file),
+            location_daylight: 0,
+            location_camera_position: 0,
+            location_fog_colour: 0,
+            location_fog_start: 0,
+            location_fog_end: 0,
+            location_animation_time: 0
         };
         result.get_uniforms();
         result
     }
+
+    pub fn load_daylight(&self, daylight: f32) {
+        ShaderBase::load_float(self.location_daylight, daylight);
+    }
+
+    pub fn load_camera_position(&self, position: &glm::TVec3<f32>) {
+        ShaderBase::load_vector_3(self.location_camera_position, position);
+    }
+
+    pub fn load_fog(&self, fog: &Fog) {
+        ShaderBase::load_vector_3(self.location_fog_colour, &fog.colour);
+        ShaderBase::load_float(self.location_fog_start, fog.start);
+        ShaderBase::load_float(self.location_fog_end, fog.end);
+    }
+
+    /// @brief Time animated block textures are played at.
+    /// @param time Seconds since the application started.
+    pub fn load_animation_time(&self, time: f32) {
+        ShaderBase::load_float(self.location_animation_time, time);
+    }
+}
+
+impl Default for ChunkShader {
+    fn default() -> Self {
+        Self::new("Chunk", "Chunk")
+    }
 }
 
 impl Shader for ChunkShader {
     fn get_uniforms(&mut self) {
         self.base.get_uniforms();
+        self.location_daylight = self.base.base.get_uniform_location("daylight");
+        self.location_camera_position = self.base.base.get_uniform_location("cameraPosition");
+        self.location_fog_colour = self.base.base.get_uniform_location("fogColour");
+        self.location_fog_start = self.base.base.get_uniform_location("fogStart");
+        self.location_fog_end = self.base.base.get_uniform_location("fogEnd");
+        self.location_animation_time = self.base.base.get_uniform_location("animationTime");
+
+        // Samplers keep their texture unit for the life of the program
+        self.base.base.use_program();
+        ShaderBase::load_int(self.base.base.get_uniform_location("sectionOrigins"), SECTION_ORIGINS_UNIT as _);
+        ShaderBase::load_int(
+            self.base.base.get_optional_uniform_location("textureRegions"),
+            TEXTURE_REGIONS_UNIT as _
+        );
+        ShaderBase::load_int(
+            self.base.base.get_uniform_location("textureAnimations"),
+            TEXTURE_ANIMATIONS_UNIT as _
+        );
     }
-}
\ No newline at end of file
+
+    fn reload(&mut self) -> bool {
+        if !self.base.reload() {
+            return false;
+        }
+        self.get_uniforms();
+        true
+    }
+}
@@ -20,4 +20,6 @@ pub mod shader_loader;
 pub mod chunk_shader;
 pub mod flora_shader;
 pub mod water_shader;
-pub mod skybox_shader;
\ No newline at end of file
+pub mod skybox_shader;
+pub mod outline_shader;
+pub mod post_effect_shader;
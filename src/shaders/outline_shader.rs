@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use gl::types::GLint;
+use crate::shaders::basic_shader::BasicShader;
+use crate::shaders::shader::{Shader, ShaderBase};
+
+/// @brief Shader drawing untextured lines in a single colour, e.g. block outlines and the crosshair.
+pub struct OutlineShader {
+    pub base: BasicShader,
+
+    location_colour: GLint
+}
+
+impl OutlineShader {
+    pub fn load_colour(&self, colour: &glm::TVec4<f32>) {
+        ShaderBase::load_vector_4(self.location_colour, colour);
+    }
+}
+
+impl Default for OutlineShader {
+    fn default() -> Self {
+        let mut result = Self {
+            base: BasicShader::new("Basic", "Outline"),
+            location_colour: 0
+        };
+        result.get_uniforms();
+        result
+    }
+}
+
+impl Shader for OutlineShader {
+    fn get_uniforms(&mut self) {
+        self.base.get_uniforms();
+        self.location_colour = self.base.base.get_uniform_location("colour");
+    }
+
+    fn reload(&mut self) -> bool {
+        if !self.base.reload() {
+            return false;
+        }
+        self.get_uniforms();
+        true
+    }
+}
\ No newline at end of file
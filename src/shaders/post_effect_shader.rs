@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use gl::types::GLint;
+use crate::shaders::shader::{Shader, ShaderBase};
+
+/// @brief Full screen shader reading the previous image of the post-processing chain.
+///
+/// Every effect shares the "Post" vertex shader; uniforms an effect does not declare are
+/// simply ignored by OpenGL.
+pub struct PostEffectShader {
+    pub base: ShaderBase,
+
+    location_resolution: GLint,
+    location_time: GLint,
+    location_gamma: GLint,
+    location_brightness: GLint,
+    location_mode: GLint
+}
+
+impl PostEffectShader {
+    pub fn new(fragment_file: &str) -> Self {
+        let mut result = Self {
+            base: ShaderBase::new("Post", fragment_file),
+            location_resolution: 0,
+            location_time: 0,
+            location_gamma: 0,
+            location_brightness: 0,
+            location_mode: 0
+        };
+        result.get_uniforms();
+        result
+    }
+
+    pub fn load_resolution(&self, resolution: &glm::TVec2<f32>) {
+        ShaderBase::load_vector_2(self.location_resolution, resolution);
+    }
+
+    pub fn load_time(&self, time: f32) {
+        ShaderBase::load_float(self.location_time, time);
+    }
+
+    pub fn load_gamma(&self, gamma: f32) {
+        ShaderBase::load_float(self.location_gamma, gamma);
+    }
+
+    pub fn load_brightness(&self, brightness: f32) {
+        ShaderBase::load_float(self.location_brightness, brightness);
+    }
+
+    pub fn load_mode(&self, mode: i32) {
+        ShaderBase::load_int(self.location_mode, mode);
+    }
+}
+
+impl Shader for PostEffectShader {
+    fn get_uniforms(&mut self) {
+        self.location_resolution = self.base.get_optional_uniform_location("resolution");
+        self.location_time = self.base.get_optional_uniform_location("time");
+        self.location_gamma = self.base.get_optional_uniform_location("gamma");
+        self.location_brightness = self.base.get_optional_uniform_location("brightness");
+        self.location_mode = self.base.get_optional_uniform_location("mode");
+    }
+
+    fn reload(&mut self) -> bool {
+        if !self.base.reload() {
+            return false;
+        }
+        self.get_uniforms();
+        true
+    }
+}
@@ -16,26 +16,81 @@
 
 extern crate nalgebra_glm as glm;
 
+use std::ffi::CString;
 use gl::types::{GLint, GLuint};
-use crate::shaders::shader_loader::load_shaders;
+use crate::shaders::shader_loader::{load_shaders, try_load_shaders};
 
 pub trait Shader {
     fn get_uniforms(&mut self);
+
+    /// @brief Recompiles the shader from its files and looks its uniforms up again.
+    /// @return Whether it compiled, the previous program being kept otherwise.
+    fn reload(&mut self) -> bool;
 }
 
 pub struct ShaderBase {
-    pub id: GLuint
+    pub id: GLuint,
+    vertex_file: String,
+    fragment_file: String
 }
 
 impl ShaderBase {
     pub fn new(vertex_file: &str, fragment_file: &str) -> Self {
         let result = Self {
-            id: load_shaders(vertex_file, fragment_file)
+            id: load_shaders(vertex_file, fragment_file),
+            vertex_file: vertex_file.to_string(),
+            fragment_file: fragment_file.to_string()
         };
         result.use_program();
         result
     }
 
+    /// @brief Replaces the program with one compiled from the current contents of its files.
+    /// The previous program stays in use if they fail to compile.
+    /// @return Whether the program was replaced.
+    pub fn reload(&mut self) -> bool {
+        match try_load_shaders(&self.vertex_file, &self.fragment_file) {
+            Ok(id) => {
+                unsafe {
+                    gl::DeleteProgram(self.id);
+                }
+                self.id = id;
+                self.use_program();
+                true
+            }
+            Err(error) => {
+                log::error!("Unable to reload shader {}/{}: {}", self.vertex_file, self.fragment_file, error);
+                false
+            }
+        }
+    }
+
+    /// @brief Location of a uniform the shader is expected to use, with a warning if it does not,
+    /// as OpenGL drops uniforms that are declared but never used.
+    /// @param name
+    pub fn get_uniform_location(&self, name: &str) -> GLint {
+        let location = self.get_optional_uniform_location(name);
+        if location == -1 {
+            log::warn!(
+                "Shader {}/{} has no active uniform named {}",
+                self.vertex_file,
+                self.fragment_file,
+                name
+            );
+        }
+        location
+    }
+
+    /// @brief Location of a uniform the shader may leave out, -1 if it does.
+    /// Loading a value at -1 is silently ignored.
+    /// @param name
+    pub fn get_optional_uniform_location(&self, name: &str) -> GLint {
+        let c_string = CString::new(name).unwrap();
+        unsafe {
+            gl::GetUniformLocation(self.id, c_string.as_ptr())
+        }
+    }
+
     pub fn load_int(location: GLint, value: i32) {
         unsafe {
             gl::Uniform1i(location, value);
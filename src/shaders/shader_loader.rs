@@ -16,81 +16,197 @@
 
 use std::ffi::CString;
 use std::ptr;
+use std::sync::Mutex;
 use gl::types::{GLchar, GLenum, GLint, GLuint};
+use lazy_static::lazy_static;
+use crate::config::Config;
 use crate::util::file_util::get_file_contents;
+use crate::world::chunk::chunk_buffer_arena::ORIGIN_PAGE_SIZE;
 
-fn compile_shader(source: *const GLchar, shader_type: GLenum) -> Result<GLuint, String> {
-    let shader_id;
+const SHADER_DIRECTORY: &str = "Shaders";
+
+/// Guards against includes that end up including themselves.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+lazy_static! {
+    static ref DEFINES: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+}
+
+/// @brief GLSL source with every `#include` resolved, and the files it was assembled from.
+struct ShaderSource {
+    text: String,
+    /// Indexed by the source string numbers of the `#line` directives, the main file first
+    files: Vec<String>
+}
+
+/// @brief Picks the `#define`s injected into every shader compiled from now on from the graphics settings,
+/// along with the constants shared with the engine.
+/// @param config
+pub fn set_defines(config: &Config) {
+    let mut defines = vec![("ORIGIN_PAGE_SIZE".to_string(), ORIGIN_PAGE_SIZE.to_string())];
+    if config.texture_arrays {
+        defines.push(("TEXTURE_ARRAYS".to_string(), "1".to_string()));
+    }
+
+    *DEFINES.lock().unwrap() = defines;
+}
+
+pub fn load_shaders(vertex_shader: &str, fragment_shader: &str) -> GLuint {
+    try_load_shaders(vertex_shader, fragment_shader).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// @brief Compiles and links a program from the given files in the shader directory.
+/// @param vertex_shader File name without extension.
+/// @param fragment_shader
+/// @return The full compile or link log when the program cannot be built.
+pub fn try_load_shaders(vertex_shader: &str, fragment_shader: &str) -> Result<GLuint, String> {
+    let vertex_source = preprocess(&format!("{}.vert", vertex_shader))?;
+    let fragment_source = preprocess(&format!("{}.frag", fragment_shader))?;
+
+    let vertex_shader_id = compile_shader(&vertex_source, gl::VERTEX_SHADER)?;
+    let fragment_shader_id = compile_shader(&fragment_source, gl::FRAGMENT_SHADER)
+        .map_err(|error| {
+            unsafe {
+                gl::DeleteShader(vertex_shader_id);
+            }
+            error
+        })?;
+
+    let shader_id = link_program(vertex_shader_id, fragment_shader_id);
 
     unsafe {
-        shader_id = gl::CreateShader(shader_type);
+        gl::DeleteShader(vertex_shader_id);
+        gl::DeleteShader(fragment_shader_id);
+    }
+
+    shader_id.map_err(|error| format!(
+        "Unable to link {}.vert and {}.frag: {}",
+        vertex_shader,
+        fragment_shader,
+        error
+    ))
+}
+
+/// @brief Reads a shader file, resolving its includes and adding the defines after `#version`.
+/// @param file_name File name in the shader directory.
+fn preprocess(file_name: &str) -> Result<ShaderSource, String> {
+    let mut result = ShaderSource {
+        text: String::new(),
+        files: Vec::new()
+    };
+    expand(file_name, &mut result, &mut Vec::new())?;
+    Ok(result)
+}
+
+/// @brief Appends a file to the source, recursing into the files it includes with `#include "name"`.
+///
+/// `#line` directives keep the line numbers of compile logs pointing into the right file,
+/// following the GLSL 3.30 convention that the line after one is numbered one past it.
+fn expand(file_name: &str, source: &mut ShaderSource, include_stack: &mut Vec<String>) -> Result<(), String> {
+    if include_stack.iter().any(|it| it == file_name) || include_stack.len() >= MAX_INCLUDE_DEPTH {
+        return Err(format!("Recursive include of {} in {}", file_name, include_stack.join(" > ")));
+    }
+
+    let text = get_file_contents(&format!("{}/{}", SHADER_DIRECTORY, file_name))?;
+    let index = source.files.len();
+    source.files.push(file_name.to_string());
+    include_stack.push(file_name.to_string());
+
+    for (number, line) in text.lines().enumerate() {
+        let number = number + 1;
+        let trimmed = line.trim();
 
-        gl::ShaderSource(shader_id, 1, &source, ptr::null());
+        if let Some(include) = trimmed.strip_prefix("#include") {
+            let include = include.trim().trim_matches('"');
+            if include.is_empty() {
+                return Err(format!("{}({}): #include without a file name", file_name, number));
+            }
+
+            source.text.push_str(&format!("#line 0 {}\n", source.files.len()));
+            expand(include, source, include_stack)?;
+            source.text.push_str(&format!("#line {} {}\n", number, index));
+        } else if trimmed.starts_with("#version") && index == 0 {
+            source.text.push_str(line);
+            source.text.push('\n');
+            for (name, value) in DEFINES.lock().unwrap().iter() {
+                source.text.push_str(&format!("#define {} {}\n", name, value));
+            }
+            source.text.push_str(&format!("#line {} {}\n", number, index));
+        } else {
+            source.text.push_str(line);
+            source.text.push('\n');
+        }
+    }
+
+    include_stack.pop();
+    Ok(())
+}
+
+fn compile_shader(source: &ShaderSource, shader_type: GLenum) -> Result<GLuint, String> {
+    let text = CString::new(source.text.as_str())
+        .map_err(|_| format!("{} contains a null character", source.files[0]))?;
+
+    unsafe {
+        let shader_id = gl::CreateShader(shader_type);
+
+        gl::ShaderSource(shader_id, 1, &text.as_ptr(), ptr::null());
         gl::CompileShader(shader_id);
 
         let mut is_success: GLint = 0;
-        let mut info_log: [GLchar; 512] = [0; 512];
-
         gl::GetShaderiv(shader_id, gl::COMPILE_STATUS, &mut is_success);
         if is_success == 0 {
-            gl::GetShaderInfoLog(
-                shader_id,
-                512,
-                ptr::null_mut(),
-                ptr::addr_of_mut!(info_log) as _
-            );
-            let info_log: Vec<_> = info_log.iter().map(|c| *c as u8).collect();
+            let mut length: GLint = 0;
+            gl::GetShaderiv(shader_id, gl::INFO_LOG_LENGTH, &mut length);
+            let mut info_log = vec![0u8; length.max(1) as usize];
+            gl::GetShaderInfoLog(shader_id, length, ptr::null_mut(), info_log.as_mut_ptr() as *mut GLchar);
+            gl::DeleteShader(shader_id);
+
+            let files: Vec<String> = source.files.iter()
+                .enumerate()
+                .map(|(i, file)| format!("{} = {}", i, file))
+                .collect();
             return Err(format!(
-                "Unable to load a shader: {}",
-                CString::new(info_log).unwrap().into_string().unwrap()
+                "Unable to compile {} (source strings: {}):\n{}",
+                source.files[0],
+                files.join(", "),
+                info_log_to_string(info_log)
             ));
         }
-    }
 
-    Ok(shader_id)
+        Ok(shader_id)
+    }
 }
 
-fn link_program(vertex_shader_id: GLuint, fragment_shader_id: GLuint) -> GLuint {
-    let id;
-    
+fn link_program(vertex_shader_id: GLuint, fragment_shader_id: GLuint) -> Result<GLuint, String> {
     unsafe {
-        id = gl::CreateProgram();
-        
+        let id = gl::CreateProgram();
+
         gl::AttachShader(id, vertex_shader_id);
         gl::AttachShader(id, fragment_shader_id);
-        
+
         gl::LinkProgram(id);
+
+        let mut is_success: GLint = 0;
+        gl::GetProgramiv(id, gl::LINK_STATUS, &mut is_success);
+        if is_success == 0 {
+            let mut length: GLint = 0;
+            gl::GetProgramiv(id, gl::INFO_LOG_LENGTH, &mut length);
+            let mut info_log = vec![0u8; length.max(1) as usize];
+            gl::GetProgramInfoLog(id, length, ptr::null_mut(), info_log.as_mut_ptr() as *mut GLchar);
+            gl::DeleteProgram(id);
+            return Err(info_log_to_string(info_log));
+        }
+
+        gl::DetachShader(id, vertex_shader_id);
+        gl::DetachShader(id, fragment_shader_id);
+        Ok(id)
     }
-    
-    id
 }
 
-pub fn load_shaders(vertex_shader: &str, fragment_shader: &str) -> GLuint {
-    let vertex_source = get_file_contents(
-        format!("Shaders/{}.vert", vertex_shader).as_str()
-    );
-    let fragment_source = get_file_contents(
-        format!("Shaders/{}.frag", fragment_shader).as_str()
-    );
-    
-    let vertex_source = CString::new(vertex_source).unwrap();
-    let fragment_source = CString::new(fragment_source).unwrap();
-    
-    let vertex_shader_id = compile_shader(
-        vertex_source.as_ptr(),
-        gl::VERTEX_SHADER
-    ).unwrap();
-    let fragment_shader_id = compile_shader(
-        fragment_source.as_ptr(),
-        gl::FRAGMENT_SHADER
-    ).unwrap();
-    
-    let shader_id = link_program(vertex_shader_id, fragment_shader_id);
-    
-    unsafe {
-        gl::DeleteShader(vertex_shader_id);
-        gl::DeleteShader(fragment_shader_id);
+fn info_log_to_string(mut info_log: Vec<u8>) -> String {
+    // Drop the terminating null character
+    if let Some(end) = info_log.iter().position(|c| *c == 0) {
+        info_log.truncate(end);
     }
-    
-    shader_id
-}
\ No newline at end of file
+    String::from_utf8_lossy(&info_log).trim_end().to_string()
+}
@@ -16,15 +16,27 @@
 
 extern crate nalgebra_glm as glm;
 
-use std::ffi::CString;
 use gl::types::GLint;
+use crate::renderer::fog::Fog;
 use crate::shaders::shader::{Shader, ShaderBase};
+use crate::world::world_time::WorldTime;
 
 pub struct SkyboxShader {
     pub base: ShaderBase,
 
     location_projection: GLint,
-    location_view: GLint
+    location_view: GLint,
+
+    location_sun_direction: GLint,
+    location_zenith_colour: GLint,
+    location_horizon_colour: GLint,
+    location_sunset_colour: GLint,
+    location_daylight: GLint,
+    location_star_visibility: GLint,
+    location_celestial_angle: GLint,
+
+    location_fog_colour: GLint,
+    location_fog_height: GLint
 }
 
 impl SkyboxShader {
@@ -32,7 +44,16 @@ impl SkyboxShader {
         Self {
             base: ShaderBase::new(vertex_file, fragment_file),
             location_projection: 0,
-            location_view: 0
+            location_view: 0,
+            location_sun_direction: 0,
+            location_zenith_colour: 0,
+            location_horizon_colour: 0,
+            location_sunset_colour: 0,
+            location_daylight: 0,
+            location_star_visibility: 0,
+            location_celestial_angle: 0,
+            location_fog_colour: 0,
+            location_fog_height: 0
         }
     }
 
@@ -47,25 +68,64 @@ impl SkyboxShader {
     pub fn load_projection_matrix(&self, proj: &glm::TMat4<f32>) {
         ShaderBase::load_matrix_4(self.location_projection, proj);
     }
+
+    pub fn load_world_time(&self, world_time: &WorldTime) {
+        ShaderBase::load_vector_3(self.location_sun_direction, &world_time.sun_direction());
+        ShaderBase::load_vector_3(self.location_zenith_colour, &world_time.zenith_colour());
+        ShaderBase::load_vector_3(self.location_horizon_colour, &world_time.horizon_colour());
+        ShaderBase::load_vector_3(self.location_sunset_colour, &world_time.sunset_colour());
+        ShaderBase::load_float(self.location_daylight, world_time.sun_visibility());
+        ShaderBase::load_float(self.location_star_visibility, world_time.star_visibility());
+        ShaderBase::load_float(self.location_celestial_angle, world_time.celestial_angle());
+    }
+
+    pub fn load_fog(&self, fog: &Fog) {
+        ShaderBase::load_vector_3(self.location_fog_colour, &fog.colour);
+        ShaderBase::load_float(self.location_fog_height, fog.sky_height);
+    }
 }
 
 impl Default for SkyboxShader {
     fn default() -> Self {
-        Self {
+        let mut result = Self {
             base: ShaderBase::new("Skybox", "Skybox"),
             location_projection: 0,
-            location_view: 0
-        }
+            location_view: 0,
+            location_sun_direction: 0,
+            location_zenith_colour: 0,
+            location_horizon_colour: 0,
+            location_sunset_colour: 0,
+            location_daylight: 0,
+            location_star_visibility: 0,
+            location_celestial_angle: 0,
+            location_fog_colour: 0,
+            location_fog_height: 0
+        };
+        result.get_uniforms();
+        result
     }
 }
 
 impl Shader for SkyboxShader {
     fn get_uniforms(&mut self) {
-        unsafe {
-            let c_string = CString::new("projectionMatrix").unwrap();
-            self.location_projection = gl::GetUniformLocation(self.base.id, c_string.as_ptr());
-            let c_string = CString::new("viewMatrix").unwrap();
-            self.location_view = gl::GetUniformLocation(self.base.id, c_string.as_ptr());
+        self.location_projection = self.base.get_uniform_location("projectionMatrix");
+        self.location_view = self.base.get_uniform_location("viewMatrix");
+        self.location_sun_direction = self.base.get_uniform_location("sunDirection");
+        self.location_zenith_colour = self.base.get_uniform_location("zenithColour");
+        self.location_horizon_colour = self.base.get_uniform_location("horizonColour");
+        self.location_sunset_colour = self.base.get_uniform_location("sunsetColour");
+        self.location_daylight = self.base.get_uniform_location("daylight");
+        self.location_star_visibility = self.base.get_uniform_location("starVisibility");
+        self.location_celestial_angle = self.base.get_uniform_location("celestialAngle");
+        self.location_fog_colour = self.base.get_uniform_location("fogColour");
+        self.location_fog_height = self.base.get_uniform_location("fogHeight");
+    }
+
+    fn reload(&mut self) -> bool {
+        if !self.base.reload() {
+            return false;
         }
+        self.get_uniforms();
+        true
     }
 }
\ No newline at end of file
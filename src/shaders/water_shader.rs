@@ -14,13 +14,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::ffi::CString;
 use gl::types::GLint;
-use crate::shaders::basic_shader::BasicShader;
+use crate::shaders::chunk_shader::ChunkShader;
 use crate::shaders::shader::{Shader, ShaderBase};
 
 pub struct WaterShader {
-    pub base: BasicShader,
+    pub base: ChunkShader,
 
     time: GLint
 }
@@ -34,7 +33,7 @@ impl WaterShader {
 impl Default for WaterShader {
     fn default() -> Self {
         let mut result = Self {
-            base: BasicShader::new("Water", "Chunk"),
+            base: ChunkShader::new("Water", "Chunk"),
             time: 0
         };
         result.get_uniforms();
@@ -45,9 +44,14 @@ impl Default for WaterShader {
 impl Shader for WaterShader {
     fn get_uniforms(&mut self) {
         self.base.get_uniforms();
-        unsafe {
-            let c_string = CString::new("globalTime").unwrap();
-            self.time = gl::GetUniformLocation(self.base.base.id, c_string.as_ptr());
+        self.time = self.base.base.base.get_uniform_location("globalTime");
+    }
+
+    fn reload(&mut self) -> bool {
+        if !self.base.reload() {
+            return false;
         }
+        self.get_uniforms();
+        true
     }
 }
\ No newline at end of file
@@ -15,4 +15,5 @@
 // limitations under the License.
 
 pub mod state_base;
-pub mod play_state;
\ No newline at end of file
+pub mod play_state;
+pub mod texture_pack_state;
@@ -20,20 +20,27 @@ use std::cell::UnsafeCell;
 use std::ptr;
 use std::rc::Rc;
 use std::sync::Arc;
+use nalgebra_glm::IVec3;
 use sfml::SfBox;
 use sfml::system::Clock;
 use sfml::window::{Event, Key};
 use sfml::window::mouse::Button;
 use crate::application::Application;
+use crate::camera::Camera;
 use crate::config::Config;
 use crate::input::keyboard::Keyboard;
 use crate::input::toggle_key::ToggleKey;
-use crate::maths::ray::Ray;
+use crate::maths::ray::{Ray, RayHit};
 use crate::player::player::Player;
+use crate::renderer::capture;
+use crate::renderer::capture::PANORAMA_SIZE;
 use crate::renderer::render_master::RenderMaster;
 use crate::states::state_base::StateBase;
+use crate::states::texture_pack_state::StateTexturePacks;
+use crate::util::file_watcher::FileWatcher;
 use crate::util::fps_counter::FPSCounter;
 use crate::util::unsafe_cell_wrapper::UnsafeCellWrapper;
+use crate::world::block::block_database::BlockDatabase;
 use crate::world::block::block_id::BlockId;
 use crate::world::event::player_dig_event::PlayerDigEvent;
 use crate::world::world::World;
@@ -45,8 +52,19 @@ pub struct StatePlay<'a> {
     keyboard: Keyboard,
     player: Player<'a>,
     world: Option<Arc<UnsafeCellWrapper<World>>>,
+    targeted_block: Option<RayHit>,
     
-    fps_counter: FPSCounter<'a>
+    fps_counter: FPSCounter<'a>,
+
+    screenshot_key: ToggleKey,
+    panorama_key: ToggleKey,
+    texture_packs_key: ToggleKey,
+    dump_atlas_key: ToggleKey,
+    reload_key: ToggleKey,
+
+    // Only watched when hot reloading is enabled
+    shader_watcher: Option<FileWatcher>,
+    block_watcher: Option<FileWatcher>
 }
 
 static mut TIMER_PTR: *mut SfBox<Clock> = ptr::null_mut();
@@ -55,6 +73,12 @@ static mut DT_PTR: *mut SfBox<Clock> = ptr::null_mut();
 static mut DRAW_GUI: bool = false;
 static mut DRAW_KEY_PTR: *mut ToggleKey = ptr::null_mut();
 
+const REACH_DISTANCE: f32 = 6.;
+
+fn block_position_to_vec3(position: &IVec3) -> glm::TVec3<f32> {
+    glm::vec3(position.x as f32, position.y as f32, position.z as f32)
+}
+
 impl<'a> StatePlay<'a> {
     pub fn new_boxed(application: Rc<UnsafeCell<Application>>, config: Config) -> Box<Self> {
         let result = Self {
@@ -62,9 +86,21 @@ impl<'a> StatePlay<'a> {
             keyboard: Keyboard::new(),
             player: Player::default(),
             world: None,
-            fps_counter: FPSCounter::new()
+            targeted_block: None,
+            fps_counter: FPSCounter::new(),
+            screenshot_key: ToggleKey::new(Key::F2),
+            panorama_key: ToggleKey::new(Key::F4),
+            texture_packs_key: ToggleKey::new(Key::F6),
+            dump_atlas_key: ToggleKey::new(Key::F7),
+            reload_key: ToggleKey::new(Key::F9),
+            shader_watcher: None,
+            block_watcher: None
         };
         let mut result = Box::new(result);
+        if config.hot_reload {
+            result.shader_watcher = Some(FileWatcher::new(&["Shaders"]));
+            result.block_watcher = Some(FileWatcher::new(&["Res/Blocks", "Res/TexturePacks"]));
+        }
         unsafe {
             result.world = Some(World::new(
                 (*application.get()).camera(),
@@ -77,6 +113,51 @@ impl<'a> StatePlay<'a> {
 
         result
     }
+
+    /// @brief Reloads the shaders, or the blocks and their textures, when the reload key is pressed
+    /// or their files changed, rebuilding every chunk mesh after the latter.
+    fn hot_reload(&mut self, renderer: &mut RenderMaster) {
+        let is_reload_requested = self.reload_key.is_key_pressed();
+
+        let has_shaders_changed = self.shader_watcher.as_mut().map_or(false, |it| it.has_changed());
+        if is_reload_requested || has_shaders_changed {
+            if renderer.reload_shaders() {
+                log::info!("Reloaded shaders.");
+            }
+        }
+
+        let has_blocks_changed = self.block_watcher.as_mut().map_or(false, |it| it.has_changed());
+        if is_reload_requested || has_blocks_changed {
+            unsafe {
+                (*self.world.as_ref().unwrap().get()).reload_blocks();
+            }
+            log::info!("Reloaded blocks and textures.");
+        }
+    }
+
+    /// @brief Saves the six cube faces seen from the camera position as
+    /// `panorama_0.png` to `panorama_5.png`.
+    fn capture_panorama(&mut self, renderer: &mut RenderMaster, camera: &Camera) {
+        let directory = capture::panorama_directory();
+
+        let mut config = camera.get_config().clone();
+        config.fov = 90;
+        config.window_x = PANORAMA_SIZE as _;
+        config.window_y = PANORAMA_SIZE as _;
+
+        let world = unsafe { &mut *self.world.as_ref().unwrap().get() };
+        for face in 0..6 {
+            let face_camera = Camera::new_fixed(config.clone(), &camera.position, &capture::panorama_rotation(face));
+            world.make_visible_meshes(&face_camera);
+            world.render_world(renderer, &face_camera);
+            renderer.capture(
+                &face_camera,
+                PANORAMA_SIZE,
+                PANORAMA_SIZE,
+                &format!("{}/panorama_{}.png", directory, face)
+            );
+        }
+    }
 }
 
 impl<'a> StateBase for StatePlay<'a> {
@@ -87,6 +168,17 @@ impl<'a> StateBase for StatePlay<'a> {
     fn handle_input(&mut self) {
         unsafe {
             self.player.handle_input((*self.application.get()).window_mut(), &self.keyboard);
+
+            if self.texture_packs_key.is_key_pressed() {
+                (*self.application.get()).queue_state(StateTexturePacks::new_boxed(
+                    Rc::clone(&self.application),
+                    Arc::clone(self.world.as_ref().unwrap())
+                ));
+            }
+        }
+
+        if self.dump_atlas_key.is_key_pressed() {
+            BlockDatabase::get().texture_atlas.dump();
         }
 
         unsafe {
@@ -95,10 +187,8 @@ impl<'a> StateBase for StatePlay<'a> {
                 TIMER_PTR = Box::leak(timer);
             }
         }
-        let mut last_position: glm::TVec3<f32> = Default::default();
-
         // Ray is cast as player's 'vision'
-        let mut ray = Ray::new(
+        let ray = Ray::new(
             &glm::vec3(
                 self.player.position.x,
                 self.player.position.y + 0.6,
@@ -106,48 +196,40 @@ impl<'a> StateBase for StatePlay<'a> {
             ),
             &self.player.rotation
         ); // Corrected for camera offset
-        while ray.length() < 6. {
-            let x = ray.end().x as i32;
-            let y = ray.end().y as i32;
-            let z = ray.end().z as i32;
-
-            let block = unsafe {
-                (*self.world.as_ref().unwrap().get()).get_block(x, y, z)
-            };
+        let world = self.world.as_ref().unwrap();
+        let hit = ray.cast(REACH_DISTANCE, |x, y, z| {
+            let block = unsafe { (*world.get()).get_block(x, y, z) };
             let id = BlockId::try_from(block.id as i32).unwrap();
+            id != BlockId::Air && id != BlockId::Water
+        });
+        self.targeted_block = hit;
 
-            if id != BlockId::Air && id != BlockId::Water {
-                unsafe {
-                    if (*TIMER_PTR).elapsed_time().as_seconds() > 0.2 {
-                        if Button::Left.is_pressed() {
-                            (*TIMER_PTR).restart();
-                            // The player "digs" the block up
-                            (*self.world.as_ref().unwrap().get()).add_event(Box::new(
-                                PlayerDigEvent::new(
-                                    Button::Left,
-                                    ray.end(),
-                                    &mut self.player
-                                )
-                            ));
-                            break;
-                        } else if Button::Right.is_pressed() {
-                            (*TIMER_PTR).restart();
-                            // The player "digs" the block up
-                            (*self.world.as_ref().unwrap().get()).add_event(Box::new(
-                                PlayerDigEvent::new(
-                                    Button::Right,
-                                    last_position,
-                                    &mut self.player
-                                )
-                            ));
-                            break;
-                        }
+        if let Some(hit) = hit {
+            unsafe {
+                if (*TIMER_PTR).elapsed_time().as_seconds() > 0.2 {
+                    if Button::Left.is_pressed() {
+                        (*TIMER_PTR).restart();
+                        // The player "digs" the block up
+                        (*world.get()).add_event(Box::new(
+                            PlayerDigEvent::new(
+                                Button::Left,
+                                block_position_to_vec3(&hit.block_position),
+                                &mut self.player
+                            )
+                        ));
+                    } else if Button::Right.is_pressed() {
+                        (*TIMER_PTR).restart();
+                        // The player places a block against the face that was hit
+                        (*world.get()).add_event(Box::new(
+                            PlayerDigEvent::new(
+                                Button::Right,
+                                block_position_to_vec3(&hit.adjacent_position()),
+                                &mut self.player
+                            )
+                        ));
                     }
                 }
             }
-            last_position = ray.end();
-
-            ray.step(0.05);
         }
     }
 
@@ -169,7 +251,7 @@ impl<'a> StateBase for StatePlay<'a> {
         unsafe {
             let arc = Arc::clone(&(*self.application.get()).camera());
             let camera = &*arc.get();
-            World::update(&Arc::clone(self.world.as_ref().unwrap()), &camera);
+            World::update(&Arc::clone(self.world.as_ref().unwrap()), &camera, delta_time);
         }
     }
 
@@ -188,13 +270,31 @@ impl<'a> StateBase for StatePlay<'a> {
                 DRAW_GUI = !DRAW_GUI;
             }
 
+            let arc = Arc::clone(&(*self.application.get()).camera());
+            let camera = &*arc.get();
+
+            if self.screenshot_key.is_key_pressed() {
+                renderer.request_screenshot();
+            }
+            self.hot_reload(renderer);
+
+            // Done before anything is queued for this frame, which would end up in the panorama
+            if self.panorama_key.is_key_pressed() {
+                self.capture_panorama(renderer, camera);
+            }
+
             if DRAW_GUI {
-                self.fps_counter.draw(renderer);
-                self.player.draw(renderer);
+                self.fps_counter.draw(renderer, &*self.world.as_ref().unwrap().get());
+                self.player.draw(renderer, self.targeted_block.as_ref());
             }
 
-            let arc = Arc::clone(&(*self.application.get()).camera());
-            let camera = &*arc.get();
+            if let Some(hit) = self.targeted_block.as_ref() {
+                renderer.draw_block_outline(&hit.block_position);
+            }
+            renderer.draw_crosshair();
+            self.player.draw_hud(renderer);
+            renderer.set_underwater(self.player.is_eyes_in_water());
+
             (*self.world.as_ref().unwrap().get()).render_world(renderer, &camera);
         }
     }
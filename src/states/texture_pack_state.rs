@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::UnsafeCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use sfml::graphics::Color;
+use sfml::system::Vector2f;
+use sfml::window::{Event, Key};
+use crate::application::Application;
+use crate::input::toggle_key::ToggleKey;
+use crate::renderer::render_master::RenderMaster;
+use crate::renderer::text_overlay_renderer::OverlayText;
+use crate::states::state_base::StateBase;
+use crate::texture::texture_atlas::TextureAtlas;
+use crate::texture::texture_pack::TexturePack;
+use crate::util::unsafe_cell_wrapper::UnsafeCellWrapper;
+use crate::world::block::block_database::BlockDatabase;
+use crate::world::world::World;
+
+const CHARACTER_SIZE: u32 = 25;
+const LINE_HEIGHT: f32 = 30.0;
+
+/// @brief Screen listing the installed texture packs over the paused world.
+///
+/// Left and right browse the packs while their stitched atlas is previewed, enter applies the
+/// highlighted one and backspace goes back to the game.
+pub struct StateTexturePacks {
+    application: Rc<UnsafeCell<Application>>,
+    world: Arc<UnsafeCellWrapper<World>>,
+
+    packs: Vec<TexturePack>,
+    selected: usize,
+    preview: Option<TextureAtlas>,
+
+    previous_key: ToggleKey,
+    next_key: ToggleKey,
+    apply_key: ToggleKey,
+    close_key: ToggleKey
+}
+
+impl StateTexturePacks {
+    pub fn new_boxed(
+        application: Rc<UnsafeCell<Application>>,
+        world: Arc<UnsafeCellWrapper<World>>
+    ) -> Box<Self> {
+        let packs: Vec<TexturePack> = TexturePack::list().iter()
+            .filter_map(|id| TexturePack::load(id))
+            .collect();
+        let current = BlockDatabase::get().texture_atlas.pack_id().to_string();
+
+        let mut result = Box::new(Self {
+            application,
+            world,
+            selected: packs.iter().position(|pack| pack.id == current).unwrap_or(0),
+            packs,
+            preview: None,
+            previous_key: ToggleKey::new(Key::Left),
+            next_key: ToggleKey::new(Key::Right),
+            apply_key: ToggleKey::new(Key::Enter),
+            close_key: ToggleKey::new(Key::Backspace)
+        });
+        result.select(result.selected);
+        result
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.preview = None;
+
+        let Some(pack) = self.packs.get(index) else {
+            return;
+        };
+        log::debug!("Previewing texture pack: {}", pack.name);
+        self.preview = Some(TextureAtlas::new(&pack.id));
+    }
+
+    /// @brief Lists the packs down the left of the screen, with the highlighted one in yellow.
+    /// @param renderer
+    fn draw_pack_list(&self, renderer: &mut RenderMaster) {
+        let line = |string: String, row: usize, colour: Color| OverlayText {
+            string,
+            position: Vector2f::new(10.0, 10.0 + row as f32 * LINE_HEIGHT),
+            character_size: CHARACTER_SIZE,
+            colour
+        };
+
+        let Some(selected) = self.packs.get(self.selected) else {
+            renderer.draw_text(line("No texture packs installed".to_string(), 0, Color::WHITE));
+            return;
+        };
+        renderer.draw_text(line(
+            format!("{} ({}px tiles)", selected.name, selected.tile_size),
+            0,
+            Color::WHITE
+        ));
+
+        let current = BlockDatabase::get().texture_atlas.pack_id();
+        for (i, pack) in self.packs.iter().enumerate() {
+            let marker = if i == self.selected { "> " } else { "  " };
+            let in_use = if pack.id == current { " (in use)" } else { "" };
+            let colour = if i == self.selected { Color::YELLOW } else { Color::WHITE };
+            renderer.draw_text(line(format!("{}{}{}", marker, pack.name, in_use), i + 2, colour));
+        }
+
+        renderer.draw_text(line(
+            "Left/Right: browse, Enter: apply, Backspace: back".to_string(),
+            self.packs.len() + 3,
+            Color::WHITE
+        ));
+    }
+}
+
+impl StateBase for StateTexturePacks {
+    fn handle_event(&mut self, _event: Event) {}
+
+    fn handle_input(&mut self) {
+        if self.packs.is_empty() {
+            return;
+        }
+
+        if self.previous_key.is_key_pressed() {
+            self.select((self.selected + self.packs.len() - 1) % self.packs.len());
+        } else if self.next_key.is_key_pressed() {
+            self.select((self.selected + 1) % self.packs.len());
+        } else if self.apply_key.is_key_pressed() {
+            let pack = &self.packs[self.selected];
+            log::info!("Switching to texture pack: {}", pack.name);
+            unsafe {
+                (*self.world.get()).set_texture_pack(&pack.id);
+            }
+        }
+
+        if self.close_key.is_key_pressed() {
+            unsafe {
+                (*self.application.get()).pop_state();
+            }
+        }
+    }
+
+    fn update(&mut self, _delta_time: f32) {}
+
+    fn render(&mut self, renderer: &mut RenderMaster) {
+        if let Some(preview) = self.preview.as_ref() {
+            renderer.draw_texture_preview(preview);
+        }
+        self.draw_pack_list(renderer);
+
+        unsafe {
+            let arc = Arc::clone(&(*self.application.get()).camera());
+            let camera = &*arc.get();
+            (*self.world.get()).render_world(renderer, &camera);
+        }
+    }
+
+    fn on_open(&mut self) {}
+}
@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use crate::texture::texture_animation::{AnimatedTexture, frame_name, TextureAnimation};
+
+/// Pixels each texture is extruded by on every side, so that neighbouring textures don't
+/// bleed into each other when sampling mipmaps.
+pub const BORDER_PADDING: u32 = 2;
+
+/// Name under which a placeholder is stitched, used for textures that do not exist.
+pub const MISSING_TEXTURE: &str = "missing";
+
+/// @brief Where a texture ended up in a stitched atlas, padding excluded, in pixels.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TextureRegion {
+    pub x: u32,
+    pub y: u32,
+    pub size: u32
+}
+
+/// @brief RGBA pixels of a stitched atlas, top row first, with the region of every texture.
+pub struct StitchedAtlas {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+    pub regions: BTreeMap<String, TextureRegion>
+}
+
+/// @brief Packs individual square textures into a single atlas.
+///
+/// Every texture is scaled to the same tile size, so they are simply laid out on a grid.
+pub struct AtlasStitcher {
+    tile_size: u32,
+    textures: BTreeMap<String, Vec<u8>>,
+    animations: BTreeMap<String, AnimatedTexture>
+}
+
+impl AtlasStitcher {
+    pub fn new(tile_size: u32) -> Self {
+        let mut result = Self {
+            tile_size,
+            textures: BTreeMap::new(),
+            animations: BTreeMap::new()
+        };
+        result.add_missing_texture();
+        result
+    }
+
+    /// @brief Adds a texture, scaled to the tile size with nearest neighbour sampling.
+    /// Only the top square of textures taller than they are wide is used.
+    /// @param name
+    /// @param width
+    /// @param height
+    /// @param pixels RGBA pixels, top row first.
+    pub fn add(&mut self, name: &str, width: u32, height: u32, pixels: &[u8]) {
+        self.remove(name);
+        let tile = self.scale_frame(width, width.min(height), 0, pixels);
+        self.textures.insert(name.to_string(), tile);
+    }
+
+    /// @brief Adds an animated texture, whose frames are the squares of the image from top to bottom.
+    /// Every frame is stitched as a texture of its own, named by `frame_name`.
+    /// @param name
+    /// @param width
+    /// @param height
+    /// @param pixels RGBA pixels, top row first.
+    /// @param animation
+    pub fn add_animated(&mut self, name: &str, width: u32, height: u32, pixels: &[u8], animation: &TextureAnimation) {
+        self.remove(name);
+        let frame_count = (height / width).max(1);
+        for frame in 0..frame_count {
+            let tile = self.scale_frame(width, width.min(height), frame * width, pixels);
+            self.textures.insert(frame_name(name, frame as _), tile);
+        }
+        self.animations.insert(name.to_string(), AnimatedTexture::new(name, animation, frame_count as _));
+    }
+
+    /// @brief Removes a texture added before, along with the frames of its animation.
+    /// @param name
+    fn remove(&mut self, name: &str) {
+        self.textures.remove(name);
+        if let Some(animation) = self.animations.remove(name) {
+            for frame in 1..animation.frame_count() {
+                self.textures.remove(&frame_name(name, frame));
+            }
+        }
+    }
+
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    /// @brief Every texture added so far with its RGBA pixels, sorted by name.
+    pub fn textures(&self) -> impl Iterator<Item = (&String, &Vec<u8>)> {
+        self.textures.iter()
+    }
+
+    /// @brief Every animated texture added so far, sorted by name.
+    pub fn animations(&self) -> impl Iterator<Item = &AnimatedTexture> {
+        self.animations.values()
+    }
+
+    pub fn stitch(&self) -> StitchedAtlas {
+        let cell_size = self.tile_size + 2 * BORDER_PADDING;
+        let columns = (self.textures.len() as f32).sqrt().ceil() as u32;
+        let rows = (self.textures.len() as u32 + columns - 1) / columns;
+
+        let mut result = StitchedAtlas {
+            width: (columns * cell_size).next_power_of_two(),
+            height: (rows * cell_size).next_power_of_two(),
+            pixels: Vec::new(),
+            regions: BTreeMap::new()
+        };
+        result.pixels = vec![0; (result.width * result.height * 4) as usize];
+
+        for (i, (name, tile)) in self.textures.iter().enumerate() {
+            let cell_x = (i as u32 % columns) * cell_size;
+            let cell_y = (i as u32 / columns) * cell_size;
+
+            let cell = pad_tile(tile, self.tile_size);
+            let row_length = (cell_size * 4) as usize;
+            for (y, row) in cell.chunks_exact(row_length).enumerate() {
+                let target = (((cell_y + y as u32) * result.width + cell_x) * 4) as usize;
+                result.pixels[target..target + row_length].copy_from_slice(row);
+            }
+
+            result.regions.insert(name.clone(), TextureRegion {
+                x: cell_x + BORDER_PADDING,
+                y: cell_y + BORDER_PADDING,
+                size: self.tile_size
+            });
+        }
+
+        result
+    }
+
+    /// @brief Scales one square of an image to the tile size with nearest neighbour sampling.
+    /// @param width Width of the whole image.
+    /// @param source_size Side of the square.
+    /// @param top First row of the square.
+    /// @param pixels RGBA pixels of the whole image.
+    fn scale_frame(&self, width: u32, source_size: u32, top: u32, pixels: &[u8]) -> Vec<u8> {
+        let mut result = Vec::with_capacity((self.tile_size * self.tile_size * 4) as usize);
+        for y in 0..self.tile_size {
+            for x in 0..self.tile_size {
+                let sx = x * source_size / self.tile_size;
+                let sy = top + y * source_size / self.tile_size;
+                let index = ((sy * width + sx) * 4) as usize;
+                result.extend_from_slice(&pixels[index..index + 4]);
+            }
+        }
+        result
+    }
+
+    /// @brief Magenta and black checkerboard that stands out wherever a texture is missing.
+    fn add_missing_texture(&mut self) {
+        let half = (self.tile_size / 2).max(1);
+        let mut pixels = Vec::with_capacity((self.tile_size * self.tile_size * 4) as usize);
+        for y in 0..self.tile_size {
+            for x in 0..self.tile_size {
+                if (x / half + y / half) % 2 == 0 {
+                    pixels.extend_from_slice(&[255, 0, 255, 255]);
+                } else {
+                    pixels.extend_from_slice(&[0, 0, 0, 255]);
+                }
+            }
+        }
+
+        self.add(MISSING_TEXTURE, self.tile_size, self.tile_size, &pixels);
+    }
+}
+
+/// @brief Surrounds a tile with its own outermost pixels, as it is laid out in an atlas.
+/// @param tile RGBA pixels, top row first.
+/// @param tile_size
+/// @return RGBA pixels of a square `BORDER_PADDING` larger on every side.
+pub fn pad_tile(tile: &[u8], tile_size: u32) -> Vec<u8> {
+    let cell_size = tile_size + 2 * BORDER_PADDING;
+    let mut result = Vec::with_capacity((cell_size * cell_size * 4) as usize);
+    for y in 0..cell_size {
+        for x in 0..cell_size {
+            let tx = x.saturating_sub(BORDER_PADDING).min(tile_size - 1);
+            let ty = y.saturating_sub(BORDER_PADDING).min(tile_size - 1);
+            let source = ((ty * tile_size + tx) * 4) as usize;
+            result.extend_from_slice(&tile[source..source + 4]);
+        }
+    }
+    result
+}
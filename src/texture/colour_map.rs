@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gl::types::GLfloat;
+use sfml::graphics::Image;
+
+/// @brief Image picking the tint of a block from the climate it is in,
+/// temperature increasing from left to right and humidity from top to bottom.
+///
+/// White leaves the texture as it is, so the map is best kept white around mild climates.
+pub struct ColourMap {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>
+}
+
+impl ColourMap {
+    pub fn from_image(image: &Image) -> Self {
+        Self {
+            width: image.size().x,
+            height: image.size().y,
+            pixels: image.pixel_data().to_vec()
+        }
+    }
+
+    /// @brief Colour for the given climate, with channels from 0 to 1.
+    /// @param temperature From 0, the coldest, to 1, the hottest.
+    /// @param humidity From 0, the driest, to 1, the wettest.
+    pub fn sample(&self, temperature: f32, humidity: f32) -> [GLfloat; 3] {
+        let x = (temperature.clamp(0.0, 1.0) * (self.width - 1) as f32).round() as u32;
+        let y = (humidity.clamp(0.0, 1.0) * (self.height - 1) as f32).round() as u32;
+        let index = ((y * self.width + x) * 4) as usize;
+
+        [
+            self.pixels[index] as GLfloat / 255.0,
+            self.pixels[index + 1] as GLfloat / 255.0,
+            self.pixels[index + 2] as GLfloat / 255.0
+        ]
+    }
+}
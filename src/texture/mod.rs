@@ -16,4 +16,9 @@
 
 pub mod texture_atlas;
 pub mod cube_texture;
-pub mod basic_texture;
\ No newline at end of file
+pub mod basic_texture;
+pub mod texture_pack;
+pub mod atlas_stitcher;
+pub mod texture_array;
+pub mod texture_animation;
+pub mod colour_map;
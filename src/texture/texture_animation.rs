@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::mem;
+use gl::types::{GLfloat, GLuint};
+
+/// @brief How an animated texture plays, read from the `.anim` file next to its frame strip.
+///
+/// The frames themselves are the squares of the texture image, stacked top to bottom.
+#[derive(Clone)]
+pub struct TextureAnimation {
+    /// Seconds each frame is shown for.
+    pub frame_time: f32,
+    /// Whether to blend each frame smoothly into the next one.
+    pub interpolate: bool,
+    /// Order the frames of the strip are shown in, every frame top to bottom when empty.
+    pub frames: Vec<usize>
+}
+
+impl Default for TextureAnimation {
+    fn default() -> Self {
+        Self {
+            frame_time: 1.0,
+            interpolate: false,
+            frames: Vec::new()
+        }
+    }
+}
+
+#[derive(Eq, PartialEq)]
+enum DecodingState {
+    Vacant,
+    FrameTime,
+    Interpolate,
+    Frames
+}
+
+impl TextureAnimation {
+    /// @brief Reads an animation file.
+    /// @param path
+    /// @return None when the file does not exist or is invalid.
+    pub fn load(path: &str) -> Option<Self> {
+        let file = File::open(path).ok()?;
+
+        let mut result = Self::default();
+        let mut state = DecodingState::Vacant;
+        for line in BufReader::new(file).lines() {
+            let line = line.ok()?;
+            let line = line.trim();
+            if state == DecodingState::Vacant {
+                match line {
+                    "FrameTime" => state = DecodingState::FrameTime,
+                    "Interpolate" => state = DecodingState::Interpolate,
+                    "Frames" => state = DecodingState::Frames,
+                    _ => {}
+                }
+            } else {
+                match state {
+                    DecodingState::FrameTime => result.frame_time = line.parse().ok()?,
+                    DecodingState::Interpolate => result.interpolate = line == "1",
+                    DecodingState::Frames => {
+                        result.frames = line.split_whitespace()
+                            .map(|frame| frame.parse().ok())
+                            .collect::<Option<_>>()?;
+                    }
+                    _ => {}
+                }
+                state = DecodingState::Vacant;
+            }
+        }
+
+        if result.frame_time <= 0.0 {
+            log::error!("Animation {} has no valid frame time.", path);
+            return None;
+        }
+        Some(result)
+    }
+}
+
+/// Texture unit the buffer texture holding the animation table is bound to.
+pub const TEXTURE_ANIMATIONS_UNIT: GLuint = 3;
+
+/// @brief Name every frame of an animated texture is stitched under, the first frame
+/// keeping the name of the texture itself.
+/// @param name
+/// @param frame
+pub fn frame_name(name: &str, frame: usize) -> String {
+    if frame == 0 {
+        name.to_string()
+    } else {
+        format!("{}#{}", name, frame)
+    }
+}
+
+/// @brief How the frames of an animated texture, each stitched as a texture of its own, are played.
+#[derive(Clone)]
+pub struct AnimatedTexture {
+    name: String,
+    frame_time: f32,
+    interpolate: bool,
+    frame_count: usize,
+    /// Frames of the strip, in the order they are shown
+    sequence: Vec<usize>
+}
+
+impl AnimatedTexture {
+    /// @param name
+    /// @param animation
+    /// @param frame_count Frames in the strip.
+    pub fn new(name: &str, animation: &TextureAnimation, frame_count: usize) -> Self {
+        let mut sequence: Vec<usize> = animation.frames.iter()
+            .copied()
+            .filter(|&frame| frame < frame_count)
+            .collect();
+        if sequence.len() != animation.frames.len() {
+            log::error!("Animation of {} refers to frames its strip does not have.", name);
+        }
+        if sequence.is_empty() {
+            sequence = (0..frame_count).collect();
+        }
+
+        Self {
+            name: name.to_string(),
+            frame_time: animation.frame_time,
+            interpolate: animation.interpolate,
+            frame_count,
+            sequence
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+}
+
+/// @brief Table the chunk shaders play animated textures from, kept in a buffer texture,
+/// so that animating them takes nothing but the time.
+///
+/// Its first texel for every texture index holds where the frame sequence of the texture starts
+/// in the table, how many frames it has, the seconds each frame is shown for and whether frames
+/// blend into each other. A texture that is not animated has a single frame, itself. The sequences
+/// follow, the texture index of one frame per texel.
+#[derive(Default)]
+pub struct AnimationTable {
+    buffer: GLuint,
+    texture: GLuint
+}
+
+impl AnimationTable {
+    /// @param texture_count Textures in the atlas or array, frames included.
+    /// @param animations
+    /// @param get_index Texture index of a texture or frame, by name.
+    pub fn new<'a>(
+        texture_count: usize,
+        animations: impl Iterator<Item = &'a AnimatedTexture>,
+        get_index: impl Fn(&str) -> GLuint
+    ) -> Self {
+        let mut table: Vec<GLfloat> = Vec::new();
+        for _ in 0..texture_count {
+            table.extend_from_slice(&[0.0, 1.0, 1.0, 0.0]);
+        }
+
+        for animation in animations {
+            let header = get_index(animation.name()) as usize * 4;
+            let start = table.len() / 4;
+            table[header..header + 4].copy_from_slice(&[
+                start as GLfloat,
+                animation.sequence.len() as GLfloat,
+                animation.frame_time,
+                if animation.interpolate { 1.0 } else { 0.0 }
+            ]);
+            for &frame in animation.sequence.iter() {
+                let index = get_index(&frame_name(animation.name(), frame));
+                table.extend_from_slice(&[index as GLfloat, 0.0, 0.0, 0.0]);
+            }
+        }
+
+        let mut result = Self::default();
+        unsafe {
+            gl::GenBuffers(1, &mut result.buffer);
+            gl::BindBuffer(gl::TEXTURE_BUFFER, result.buffer);
+            gl::BufferData(
+                gl::TEXTURE_BUFFER,
+                mem::size_of_val(table.as_slice()) as _,
+                table.as_ptr() as _,
+                gl::STATIC_DRAW
+            );
+            gl::BindBuffer(gl::TEXTURE_BUFFER, 0);
+
+            gl::GenTextures(1, &mut result.texture);
+            gl::BindTexture(gl::TEXTURE_BUFFER, result.texture);
+            gl::TexBuffer(gl::TEXTURE_BUFFER, gl::RGBA32F, result.buffer);
+            gl::BindTexture(gl::TEXTURE_BUFFER, 0);
+        }
+        result
+    }
+
+    /// @brief Binds the table to its own texture unit, leaving the first one active.
+    pub fn bind(&self) {
+        unsafe {
+            gl::ActiveTexture(gl::TEXTURE0 + TEXTURE_ANIMATIONS_UNIT);
+            gl::BindTexture(gl::TEXTURE_BUFFER, self.texture);
+            gl::ActiveTexture(gl::TEXTURE0);
+        }
+    }
+}
+
+impl Drop for AnimationTable {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteTextures(1, &self.texture);
+            gl::DeleteBuffers(1, &self.buffer);
+        }
+    }
+}
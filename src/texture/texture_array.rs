@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::ffi::CStr;
+use gl::types::{GLenum, GLfloat, GLint, GLuint};
+use crate::texture::atlas_stitcher::MISSING_TEXTURE;
+use crate::texture::texture_animation::AnimationTable;
+use crate::texture::texture_pack::TexturePack;
+use crate::world::chunk::chunk_mesh::MAX_TEXTURE_INDEX;
+
+// From EXT_texture_filter_anisotropic, core only since OpenGL 4.6
+const TEXTURE_MAX_ANISOTROPY: GLenum = 0x84FE;
+const MAX_TEXTURE_MAX_ANISOTROPY: GLenum = 0x84FF;
+
+/// @brief Block textures of a texture pack as the layers of a `GL_TEXTURE_2D_ARRAY`.
+///
+/// Unlike the atlas, every texture gets a full mip chain of its own and can repeat
+/// across a face, since texture coordinates only select a position within a layer.
+#[derive(Default)]
+pub struct TextureArray {
+    id: GLuint,
+    pack_id: String,
+    tile_size: u32,
+    layers: BTreeMap<String, GLint>,
+    animation_table: AnimationTable
+}
+
+impl TextureArray {
+    /// @brief Uploads the textures of the given pack, taking those it lacks from the default pack.
+    /// @param pack_id Directory name of the texture pack.
+    /// @param anisotropy Maximum anisotropic filtering level, 1 to disable it.
+    pub fn new(pack_id: &str, anisotropy: f32) -> Self {
+        let pack = TexturePack::load_or_default(pack_id);
+        let textures = pack.collect_textures();
+        let tile_size = textures.tile_size();
+
+        let mut result = Self::default();
+        result.pack_id = pack.id;
+        result.tile_size = tile_size;
+        let mut pixels = Vec::new();
+        // The placeholder comes first, so the layer every lookup falls back to always fits
+        let ordered = textures.textures().filter(|(name, _)| *name == MISSING_TEXTURE)
+            .chain(textures.textures().filter(|(name, _)| *name != MISSING_TEXTURE));
+        for (layer, (name, texture)) in ordered.enumerate() {
+            result.layers.insert(name.clone(), layer as _);
+            pixels.extend_from_slice(texture);
+        }
+        if result.layers.len() > MAX_TEXTURE_INDEX as usize + 1 {
+            log::error!(
+                "Texture pack {} has {} textures and animation frames, only {} fit in a chunk vertex",
+                result.pack_id,
+                result.layers.len(),
+                MAX_TEXTURE_INDEX + 1
+            );
+        }
+        let animation_table = AnimationTable::new(
+            result.layers.len(),
+            textures.animations(),
+            // The table has room for every frame, only chunk vertices are limited
+            |name| result.layers.get(name).copied().unwrap_or(0) as _
+        );
+        result.animation_table = animation_table;
+
+        unsafe {
+            gl::GenTextures(1, &mut result.id);
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D_ARRAY, result.id);
+
+            gl::TexImage3D(
+                gl::TEXTURE_2D_ARRAY,
+                0,
+                gl::RGBA8 as _,
+                tile_size as _,
+                tile_size as _,
+                result.layers.len() as _,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                pixels.as_ptr() as _
+            );
+
+            gl::GenerateMipmap(gl::TEXTURE_2D_ARRAY);
+            gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_MIN_FILTER, gl::NEAREST_MIPMAP_LINEAR as _);
+            gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_MAG_FILTER, gl::NEAREST as _);
+            gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_WRAP_S, gl::REPEAT as _);
+            gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_WRAP_T, gl::REPEAT as _);
+
+            if anisotropy > 1.0 && is_anisotropic_filtering_supported() {
+                let mut max_anisotropy: GLfloat = 1.0;
+                gl::GetFloatv(MAX_TEXTURE_MAX_ANISOTROPY, &mut max_anisotropy);
+                gl::TexParameterf(gl::TEXTURE_2D_ARRAY, TEXTURE_MAX_ANISOTROPY, anisotropy.min(max_anisotropy));
+            }
+        }
+
+        result
+    }
+
+    pub fn bind_texture(&self) {
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D_ARRAY, self.id);
+        }
+    }
+
+    /// @brief Binds the animation table to its own texture unit, leaving the first one active.
+    pub fn bind_animations(&self) {
+        self.animation_table.bind();
+    }
+
+    /// @brief Directory name of the pack actually in use.
+    pub fn pack_id(&self) -> &str {
+        &self.pack_id
+    }
+
+    /// @brief Layer holding the named texture, the placeholder's layer for unknown names and
+    /// those past what a chunk vertex can hold.
+    /// @param name
+    pub fn get_layer(&self, name: &str) -> GLint {
+        self.layers.get(name)
+            .copied()
+            .filter(|layer| *layer as GLuint <= MAX_TEXTURE_INDEX)
+            .unwrap_or(0)
+    }
+}
+
+impl Drop for TextureArray {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteTextures(1, &self.id);
+        }
+    }
+}
+
+fn is_anisotropic_filtering_supported() -> bool {
+    unsafe {
+        let mut count = 0;
+        gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut count);
+        (0..count as GLuint).any(|i| {
+            let extension = CStr::from_ptr(gl::GetStringi(gl::EXTENSIONS, i) as _);
+            matches!(
+                extension.to_bytes(),
+                b"GL_EXT_texture_filter_anisotropic" | b"GL_ARB_texture_filter_anisotropic"
+            )
+        })
+    }
+}
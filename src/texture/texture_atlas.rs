@@ -14,56 +14,94 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::{fs, mem};
+use std::io::Write;
 use gl::types::{GLfloat, GLuint};
 use sfml::graphics::Image;
-use sfml::system::Vector2i;
+use crate::texture::atlas_stitcher::{MISSING_TEXTURE, TextureRegion};
+use crate::texture::texture_animation::AnimationTable;
+use crate::texture::texture_pack::TexturePack;
+use crate::world::chunk::chunk_mesh::MAX_TEXTURE_INDEX;
 
-/// @brief Texture atlas that pulls texture data from existing files and maps them appropraitely.
+pub const ATLAS_DUMP_DIRECTORY: &str = "Debug";
+
+/// Texture unit the buffer texture holding the region table is bound to.
+pub const TEXTURE_REGIONS_UNIT: GLuint = 2;
+
+/// @brief Texture atlas stitched from the individual textures of a texture pack,
+/// mapping texture names to their coordinates.
+///
+/// Chunk vertices only carry the index of their texture, the vertex shader looks its
+/// coordinates up in a table of every region kept in a buffer texture.
 #[derive(Default)]
 pub struct TextureAtlas {
     id: GLuint,
-    image_size: i32,
-    individual_texture_size: i32
+    pack_id: String,
+    image_width: i32,
+    image_height: i32,
+    regions: BTreeMap<String, TextureRegion>,
+    region_indices: BTreeMap<String, GLuint>,
+    region_buffer: GLuint,
+    region_texture: GLuint,
+    animation_table: AnimationTable
 }
 
-const IMAGE_SIZE: i32 = 256;
-const INDIVIDUAL_TEXTURE_SIZE: i32 = 16;
-const TEX_PER_ROW: GLfloat = IMAGE_SIZE as GLfloat / INDIVIDUAL_TEXTURE_SIZE as GLfloat;
-const INDV_TEX_SIZE: GLfloat = 1f32 / TEX_PER_ROW;
-const PIXEL_SIZE: GLfloat = 1f32 / IMAGE_SIZE as f32;
-
 impl TextureAtlas {
-    pub fn new(texture_file_name: &str) -> Self {
-        let mut result = Self::default();
-
-        let i = Image::from_file(format!("Res/Textures/{}.png", texture_file_name).as_str())
-            .expect(format!("Unable to open image: {}", texture_file_name).as_str());
-        result.load_from_image(&i);
+    /// @brief Stitches the textures of the given pack, taking those it lacks from the default pack.
+    /// Falls back to the default pack entirely if the pack cannot be loaded.
+    /// @param pack_id Directory name of the texture pack.
+    pub fn new(pack_id: &str) -> Self {
+        let pack = TexturePack::load_or_default(pack_id);
+        let textures = pack.collect_textures();
+        let atlas = textures.stitch();
 
-        result.image_size = IMAGE_SIZE;
-        result.individual_texture_size = INDIVIDUAL_TEXTURE_SIZE;
+        let mut result = Self::default();
+        result.load_from_pixels(atlas.width, atlas.height, &atlas.pixels);
+        result.pack_id = pack.id;
+        result.image_width = atlas.width as _;
+        result.image_height = atlas.height as _;
+        result.regions = atlas.regions;
+        result.load_region_table();
+        if result.regions.len() > MAX_TEXTURE_INDEX as usize + 1 {
+            log::error!(
+                "Texture pack {} has {} textures and animation frames, only {} fit in a chunk vertex",
+                result.pack_id,
+                result.regions.len(),
+                MAX_TEXTURE_INDEX + 1
+            );
+        }
+        let animation_table = AnimationTable::new(
+            result.regions.len(),
+            textures.animations(),
+            // The table has room for every frame, only chunk vertices are limited
+            |name| result.region_indices.get(name).copied().unwrap_or(0)
+        );
+        result.animation_table = animation_table;
 
         result
     }
 
     pub fn load_from_image(&mut self, image: &Image) {
+        self.load_from_pixels(image.size().x, image.size().y, image.pixel_data());
+    }
+
+    pub fn load_from_pixels(&mut self, width: u32, height: u32, pixels: &[u8]) {
         unsafe {
             gl::GenTextures(1, &mut self.id);
             gl::ActiveTexture(gl::TEXTURE0);
             gl::BindTexture(gl::TEXTURE_2D, self.id);
 
-            let pixel_data = image.pixel_data();
-            let pixel_data_vec = Vec::from(pixel_data);
             gl::TexImage2D(
                 gl::TEXTURE_2D,
                 0,
                 gl::RGBA as _,
-                image.size().x as _,
-                image.size().y as _,
+                width as _,
+                height as _,
                 0,
                 gl::RGBA,
                 gl::UNSIGNED_BYTE,
-                pixel_data_vec.as_ptr() as _
+                pixels.as_ptr() as _
             );
 
             gl::GenerateMipmap(gl::TEXTURE_2D);
@@ -75,26 +113,121 @@ impl TextureAtlas {
         }
     }
 
-    pub fn load_from_file(&mut self, file: &str) {
-        let i = Image::from_file(format!("Res/Textures/{}.png", file).as_str())
-            .expect(format!("Unable to load BasicTexture: {}", file).as_str());
-        self.load_from_image(&i);
-    }
-
     pub fn bind_texture(&self) {
         unsafe {
             gl::BindTexture(gl::TEXTURE_2D, self.id);
         }
     }
 
-    pub fn get_texture(&self, coords: &Vector2i) -> [GLfloat; 8] {
-        let x_min = coords.x as f32 * INDV_TEX_SIZE + 0.5 * PIXEL_SIZE;
-        let y_min = coords.y as f32 * INDV_TEX_SIZE + 0.5 * PIXEL_SIZE;
+    /// @brief Binds the region table to its own texture unit, leaving the first one active.
+    pub fn bind_regions(&self) {
+        unsafe {
+            gl::ActiveTexture(gl::TEXTURE0 + TEXTURE_REGIONS_UNIT);
+            gl::BindTexture(gl::TEXTURE_BUFFER, self.region_texture);
+            gl::ActiveTexture(gl::TEXTURE0);
+        }
+    }
+
+    pub fn id(&self) -> GLuint {
+        self.id
+    }
 
-        let x_max = x_min + INDV_TEX_SIZE - PIXEL_SIZE;
-        let y_max = y_min + INDV_TEX_SIZE - PIXEL_SIZE;
+    /// @brief Directory name of the pack actually in use.
+    pub fn pack_id(&self) -> &str {
+        &self.pack_id
+    }
+
+    /// @brief Binds the animation table to its own texture unit, leaving the first one active.
+    pub fn bind_animations(&self) {
+        self.animation_table.bind();
+    }
 
-        [x_max, y_max, x_min, y_max, x_min, y_min, x_max, y_min]
+    /// @brief Index of the named texture in the region table.
+    /// Unknown names, and those past what a chunk vertex can hold, get the placeholder texture.
+    /// @param name
+    pub fn get_texture_index(&self, name: &str) -> GLuint {
+        self.region_indices.get(name)
+            .copied()
+            .filter(|index| *index <= MAX_TEXTURE_INDEX)
+            .unwrap_or(0)
+    }
+
+    /// @brief Uploads the lowest and highest texture coordinates of every region,
+    /// the placeholder first and the others in the order of their names.
+    fn load_region_table(&mut self) {
+        let mut table: Vec<GLfloat> = Vec::with_capacity(self.regions.len() * 4);
+        // The placeholder comes first, so the index every lookup falls back to always fits
+        let names = self.regions.keys().filter(|name| *name == MISSING_TEXTURE)
+            .chain(self.regions.keys().filter(|name| *name != MISSING_TEXTURE));
+        for (index, name) in names.enumerate() {
+            let region = &self.regions[name];
+            // The padding around each texture keeps the edges from bleeding, no inset needed
+            table.extend_from_slice(&[
+                region.x as GLfloat / self.image_width as GLfloat,
+                region.y as GLfloat / self.image_height as GLfloat,
+                (region.x + region.size) as GLfloat / self.image_width as GLfloat,
+                (region.y + region.size) as GLfloat / self.image_height as GLfloat
+            ]);
+            self.region_indices.insert(name.clone(), index as _);
+        }
+
+        unsafe {
+            gl::GenBuffers(1, &mut self.region_buffer);
+            gl::BindBuffer(gl::TEXTURE_BUFFER, self.region_buffer);
+            gl::BufferData(
+                gl::TEXTURE_BUFFER,
+                mem::size_of_val(table.as_slice()) as _,
+                table.as_ptr() as _,
+                gl::STATIC_DRAW
+            );
+            gl::BindBuffer(gl::TEXTURE_BUFFER, 0);
+
+            gl::GenTextures(1, &mut self.region_texture);
+            gl::BindTexture(gl::TEXTURE_BUFFER, self.region_texture);
+            gl::TexBuffer(gl::TEXTURE_BUFFER, gl::RGBA32F, self.region_buffer);
+            gl::BindTexture(gl::TEXTURE_BUFFER, 0);
+        }
+    }
+
+    /// @brief Writes the stitched atlas and the region of every texture in the debug directory.
+    /// @return Whether both files were written.
+    pub fn dump(&self) -> bool {
+        let mut pixels = vec![0u8; (self.image_width * self.image_height * 4) as usize];
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, self.id);
+            gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+            gl::GetTexImage(gl::TEXTURE_2D, 0, gl::RGBA, gl::UNSIGNED_BYTE, pixels.as_mut_ptr() as _);
+        }
+
+        if fs::create_dir_all(ATLAS_DUMP_DIRECTORY).is_err() {
+            log::error!("Unable to create directory: {}", ATLAS_DUMP_DIRECTORY);
+            return false;
+        }
+        let image_path = format!("{}/Atlas_{}.png", ATLAS_DUMP_DIRECTORY, self.pack_id);
+        let regions_path = format!("{}/Atlas_{}.txt", ATLAS_DUMP_DIRECTORY, self.pack_id);
+
+        let image = unsafe {
+            Image::create_from_pixels(self.image_width as _, self.image_height as _, &pixels)
+        };
+        if !image.map_or(false, |image| image.save_to_file(&image_path)) {
+            log::error!("Unable to save {}", image_path);
+            return false;
+        }
+
+        let regions = fs::File::create(&regions_path).and_then(|mut file| {
+            writeln!(file, "{}x{}", self.image_width, self.image_height)?;
+            for (name, region) in self.regions.iter() {
+                writeln!(file, "{} {} {} {}", name, region.x, region.y, region.size)?;
+            }
+            Ok(())
+        });
+        if regions.is_err() {
+            log::error!("Unable to save {}", regions_path);
+            return false;
+        }
+
+        log::info!("Saved {} and {}", image_path, regions_path);
+        true
     }
 }
 
@@ -102,6 +235,8 @@ impl Drop for TextureAtlas {
     fn drop(&mut self) {
         unsafe {
             gl::DeleteTextures(1, &self.id);
+            gl::DeleteTextures(1, &self.region_texture);
+            gl::DeleteBuffers(1, &self.region_buffer);
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use sfml::graphics::Image;
+use crate::texture::atlas_stitcher::AtlasStitcher;
+use crate::texture::colour_map::ColourMap;
+use crate::texture::texture_animation::TextureAnimation;
+
+pub const TEXTURE_PACK_DIRECTORY: &str = "Res/TexturePacks";
+
+/// Pack every other pack falls back to for the tiles it does not provide.
+pub const DEFAULT_TEXTURE_PACK: &str = "DefaultPack";
+
+const MANIFEST_FILE: &str = "Pack.txt";
+const BLOCK_TEXTURE_DIRECTORY: &str = "Blocks";
+const COLOUR_MAP_DIRECTORY: &str = "ColourMaps";
+
+/// @brief A directory holding block textures, one PNG per texture, and the manifest describing them.
+#[derive(Clone)]
+pub struct TexturePack {
+    /// Directory name, which is also what the config refers to.
+    pub id: String,
+    /// Display name from the manifest.
+    pub name: String,
+    /// Size every texture of the pack is stitched at, in pixels.
+    pub tile_size: u32
+}
+
+#[derive(Eq, PartialEq)]
+enum DecodingState {
+    Vacant,
+    Name,
+    TileSize
+}
+
+impl TexturePack {
+    /// @brief Reads the manifest of the pack in the given directory.
+    /// @param id Directory name of the pack.
+    /// @return None when the pack does not exist or its manifest is invalid.
+    pub fn load(id: &str) -> Option<Self> {
+        let file = File::open(format!("{}/{}/{}", TEXTURE_PACK_DIRECTORY, id, MANIFEST_FILE)).ok()?;
+
+        let mut result = Self {
+            id: id.to_string(),
+            name: id.to_string(),
+            tile_size: 0
+        };
+
+        let mut state = DecodingState::Vacant;
+        for line in BufReader::new(file).lines() {
+            let line = line.ok()?;
+            let line = line.trim();
+            if state == DecodingState::Vacant {
+                match line {
+                    "Name" => state = DecodingState::Name,
+                    "TileSize" => state = DecodingState::TileSize,
+                    _ => {}
+                }
+            } else {
+                match state {
+                    DecodingState::Name => result.name = line.to_string(),
+                    DecodingState::TileSize => result.tile_size = line.parse().ok()?,
+                    _ => {}
+                }
+                state = DecodingState::Vacant;
+            }
+        }
+
+        if result.tile_size == 0 {
+            log::error!("Texture pack {} has no valid tile size.", id);
+            return None;
+        }
+        Some(result)
+    }
+
+    /// @brief Loads the pack, or the default pack if it cannot be loaded.
+    /// @param id
+    pub fn load_or_default(id: &str) -> Self {
+        let pack = Self::load(id).or_else(|| {
+            log::error!("Unable to load texture pack: {}, using the default pack.", id);
+            Self::load(DEFAULT_TEXTURE_PACK)
+        });
+        pack.expect("Unable to load the default texture pack.")
+    }
+
+    /// @brief Every block texture of the pack at its tile size, ready to be stitched.
+    /// Textures the pack lacks are taken from the default pack, along with their animation.
+    pub fn collect_textures(&self) -> AtlasStitcher {
+        log::info!("Loading texture pack: {}", self.name);
+
+        let default_pack = Self::load(DEFAULT_TEXTURE_PACK)
+            .expect("Unable to load the default texture pack.");
+        let names: BTreeSet<String> = default_pack.texture_names().into_iter()
+            .chain(self.texture_names())
+            .collect();
+
+        let mut result = AtlasStitcher::new(self.tile_size);
+        for name in names.iter() {
+            let source = [self, &default_pack].into_iter()
+                .find_map(|pack| Some((pack, pack.load_texture(name)?)));
+            let Some((pack, image)) = source else {
+                log::error!("Unable to load block texture: {}", name);
+                continue;
+            };
+
+            let size = image.size();
+            match pack.load_animation(name) {
+                Some(animation) => result.add_animated(name, size.x, size.y, image.pixel_data(), &animation),
+                None => result.add(name, size.x, size.y, image.pixel_data())
+            }
+        }
+        result
+    }
+
+    /// @brief Every colour map of the pack by name, those it lacks taken from the default pack.
+    pub fn collect_colour_maps(&self) -> BTreeMap<String, ColourMap> {
+        let default_pack = Self::load(DEFAULT_TEXTURE_PACK)
+            .expect("Unable to load the default texture pack.");
+        let names: BTreeSet<String> = default_pack.file_names(COLOUR_MAP_DIRECTORY, "png").into_iter()
+            .chain(self.file_names(COLOUR_MAP_DIRECTORY, "png"))
+            .collect();
+
+        let mut result = BTreeMap::new();
+        for name in names {
+            let path = [self, &default_pack].into_iter()
+                .map(|pack| pack.file_path(COLOUR_MAP_DIRECTORY, &name, "png"))
+                .find(|path| Path::new(path).exists());
+            match path.and_then(|path| Image::from_file(&path)) {
+                Some(image) => {
+                    result.insert(name, ColourMap::from_image(&image));
+                }
+                None => log::error!("Unable to load colour map: {}", name)
+            }
+        }
+        result
+    }
+
+    /// @brief Ids of every installed pack, the default one first.
+    pub fn list() -> Vec<String> {
+        let mut result: Vec<String> = fs::read_dir(TEXTURE_PACK_DIRECTORY)
+            .map(|entries| entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().join(MANIFEST_FILE).exists())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect())
+            .unwrap_or_default();
+
+        result.sort_by_key(|id| (id != DEFAULT_TEXTURE_PACK, id.clone()));
+        result
+    }
+
+    /// @brief Names of the block textures the pack provides, without extension.
+    pub fn texture_names(&self) -> Vec<String> {
+        self.file_names(BLOCK_TEXTURE_DIRECTORY, "png")
+    }
+
+    pub fn load_texture(&self, name: &str) -> Option<Image> {
+        let path = self.file_path(BLOCK_TEXTURE_DIRECTORY, name, "png");
+        if !Path::new(&path).exists() {
+            return None;
+        }
+
+        Image::from_file(&path)
+    }
+
+    /// @brief Animation of the named texture, if the pack has an animation file for it.
+    /// @param name
+    pub fn load_animation(&self, name: &str) -> Option<TextureAnimation> {
+        let path = self.file_path(BLOCK_TEXTURE_DIRECTORY, name, "anim");
+        if !Path::new(&path).exists() {
+            return None;
+        }
+
+        let result = TextureAnimation::load(&path);
+        if result.is_none() {
+            log::error!("Unable to load texture animation: {}", path);
+        }
+        result
+    }
+
+    fn file_path(&self, directory: &str, name: &str, extension: &str) -> String {
+        format!(
+            "{}/{}/{}/{}.{}",
+            TEXTURE_PACK_DIRECTORY,
+            self.id,
+            directory,
+            name,
+            extension
+        )
+    }
+
+    /// @brief Names of the files with the given extension in a directory of the pack, without extension.
+    fn file_names(&self, directory: &str, extension: &str) -> Vec<String> {
+        fs::read_dir(format!("{}/{}/{}", TEXTURE_PACK_DIRECTORY, self.id, directory))
+            .map(|entries| entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().map_or(false, |it| it == extension))
+                .filter_map(|path| path.file_stem()?.to_str().map(String::from))
+                .collect())
+            .unwrap_or_default()
+    }
+}
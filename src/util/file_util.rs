@@ -16,7 +16,6 @@
 
 use std::fs;
 
-pub fn get_file_contents(file_path: &str) -> String {
-    fs::read_to_string(file_path).expect(
-        format!("Unable to open file: {}", file_path).as_str())
+pub fn get_file_contents(file_path: &str) -> Result<String, String> {
+    fs::read_to_string(file_path).map_err(|error| format!("Unable to open file: {}: {}", file_path, error))
 }
\ No newline at end of file
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use sfml::SfBox;
+use sfml::system::Clock;
+
+/// Seconds between two scans of the watched directories.
+const POLL_INTERVAL: f32 = 0.5;
+
+/// @brief Notices files being modified, added or removed in a set of directories by
+/// periodically scanning them, so that nothing beyond the standard library is needed.
+pub struct FileWatcher {
+    directories: Vec<String>,
+    snapshot: Vec<(String, SystemTime)>,
+    poll_timer: SfBox<Clock>
+}
+
+impl FileWatcher {
+    /// @param directories Directories to watch, including their subdirectories.
+    pub fn new(directories: &[&str]) -> Self {
+        let directories: Vec<String> = directories.iter().map(|it| it.to_string()).collect();
+        Self {
+            snapshot: scan(&directories),
+            directories,
+            poll_timer: Clock::start()
+        }
+    }
+
+    /// @brief Whether anything changed in the watched directories since the last change was reported.
+    /// Only scans them every `POLL_INTERVAL` seconds, returning false in between.
+    pub fn has_changed(&mut self) -> bool {
+        if self.poll_timer.elapsed_time().as_seconds() < POLL_INTERVAL {
+            return false;
+        }
+        self.poll_timer.restart();
+
+        let snapshot = scan(&self.directories);
+        if snapshot == self.snapshot {
+            return false;
+        }
+        self.snapshot = snapshot;
+        true
+    }
+}
+
+/// @brief Path and modification time of every file in the given directories, sorted by path.
+fn scan(directories: &[String]) -> Vec<(String, SystemTime)> {
+    let mut result = Vec::new();
+    for directory in directories {
+        scan_directory(Path::new(directory), &mut result);
+    }
+    result.sort();
+    result
+}
+
+fn scan_directory(directory: &Path, result: &mut Vec<(String, SystemTime)>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            scan_directory(&path, result);
+        } else if let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) {
+            result.push((path.to_string_lossy().into_owned(), modified));
+        }
+    }
+}
@@ -18,6 +18,12 @@ use sfml::graphics::{Color, Font, Text, Transformable};
 use sfml::SfBox;
 use sfml::system::{Clock, Vector2f};
 use crate::renderer::render_master::RenderMaster;
+use crate::renderer::text_overlay_renderer::OverlayText;
+use crate::world::chunk::chunk_buffer_arena::ChunkBufferArena;
+use crate::world::world::World;
+
+const KILOBYTE: f32 = 1024.0;
+const MEGABYTE: f32 = 1024.0 * 1024.0;
 
 /// @brief Generally obsolete FPS counter associated with SFML.
 pub struct FPSCounter<'a> {
@@ -77,7 +83,56 @@ impl<'a> FPSCounter<'a> {
         }
     }
 
-    pub fn draw(&mut self, _renderer: &RenderMaster) {
+    pub fn draw(&mut self, renderer: &mut RenderMaster, world: &World) {
         self.text.set_string(format!("FPS: {}", self.fps).as_str());
+
+        let stats = ChunkBufferArena::get().stats();
+        renderer.draw_text(Self::debug_line(1, format!(
+            "Chunk buffers: {:.1}/{:.1} MB vertices {:.1}/{:.1} MB indices, {} meshes, {} draws for {} meshes",
+            ChunkBufferArena::vertex_bytes(stats.vertices_used) as f32 / MEGABYTE,
+            ChunkBufferArena::vertex_bytes(stats.vertex_capacity) as f32 / MEGABYTE,
+            ChunkBufferArena::index_bytes(stats.indices_used) as f32 / MEGABYTE,
+            ChunkBufferArena::index_bytes(stats.index_capacity) as f32 / MEGABYTE,
+            stats.allocations,
+            stats.draw_calls,
+            stats.drawn_meshes
+        )));
+
+        let uploads = world.get_chunk_manager().get_upload_stats();
+        renderer.draw_text(Self::debug_line(2, format!(
+            "Mesh queue: {} sections, {} LODs",
+            uploads.queued_sections,
+            uploads.queued_lods
+        )));
+        renderer.draw_text(Self::debug_line(3, format!(
+            "Uploaded last frame: {} meshes, {:.1}/{} KB in {:.2}/{} ms",
+            uploads.uploaded_meshes,
+            uploads.uploaded_bytes as f32 / KILOBYTE,
+            Self::budget_limit(uploads.budget.max_bytes as f32 / KILOBYTE),
+            uploads.upload_time,
+            Self::budget_limit(uploads.budget.max_time)
+        )));
+    }
+
+    /// @brief An upload budget limit as shown on the overlay, 0 meaning there is none.
+    /// @param limit
+    fn budget_limit(limit: f32) -> String {
+        if limit > 0.0 {
+            format!("{}", limit)
+        } else {
+            String::from("unlimited")
+        }
+    }
+
+    /// @brief A line of the debug overlay, counted from the top of the screen.
+    /// @param row
+    /// @param string
+    fn debug_line(row: u32, string: String) -> OverlayText {
+        OverlayText {
+            string,
+            position: Vector2f::new(10.0, 10.0 + 30.0 * row as f32),
+            character_size: 25,
+            colour: Color::WHITE
+        }
     }
 }
\ No newline at end of file
@@ -22,6 +22,7 @@ pub mod array2d;
 pub mod random;
 pub mod file_util;
 pub mod unsafe_cell_wrapper;
+pub mod file_watcher;
 
 pub fn ivec3_to_vector3i(ivec3: IVec3) -> Vector3i {
     Vector3i::new(ivec3.x, ivec3.y, ivec3.z)
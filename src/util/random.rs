@@ -47,6 +47,13 @@ impl RandomSingleton {
         &INSTANCE
     }
 
+    /// @brief Makes everything drawn from the singleton reproducible, including the world seed
+    /// as long as no terrain was generated yet.
+    /// @param seed
+    pub fn set_seed(&self, seed: u64) {
+        *self.random_engine.lock().unwrap() = StdRng::seed_from_u64(seed);
+    }
+
     pub fn int_in_range<T: SampleUniform>(&self, range: impl SampleRange<T>) -> T {
         self.random_engine.lock().unwrap().gen_range(range)
     }
@@ -77,4 +84,18 @@ impl Default for Random {
         let duration = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
         Self::new_with_seed(duration.as_secs())
     }
-}
\ No newline at end of file
+}
+
+/// @brief Seed of what a generator places in one chunk, the same whichever order chunks load in.
+/// @param world_seed
+/// @param x Chunk x
+/// @param z Chunk z
+/// @param salt Picked by each caller, the carver and every ore feature passing its own, so their
+/// random streams for a chunk don't correlate.
+pub fn chunk_seed(world_seed: i32, x: i32, z: i32, salt: i64) -> u64 {
+    (world_seed as i64)
+        .wrapping_mul(341873128712)
+        .wrapping_add((x as i64).wrapping_mul(132897987541))
+        .wrapping_add((z as i64).wrapping_mul(1000003))
+        .wrapping_add(salt.wrapping_mul(7919)) as u64
+}
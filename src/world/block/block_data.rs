@@ -14,9 +14,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+extern crate nalgebra_glm as glm;
+
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use sfml::system::Vector2i;
+use crate::physics::aabb::AABB;
 use crate::world::block::block_id::BlockId;
 
 /// @brief Allocates meshes to cubes and non-cube entities.
@@ -34,18 +36,42 @@ pub enum BlockShaderType {
     Flora = 2
 }
 
+/// @brief How the block's texture alpha is used when rendering.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum BlockTransparency {
+    /// Fully covers whatever is behind it.
+    Opaque = 0,
+    /// Pixels are either fully visible or discarded (leaves, flora).
+    Cutout = 1,
+    /// Blended with whatever is behind it, drawn back-to-front after the terrain (water, glass).
+    Blended = 2
+}
+
+/// @brief Biome dependent colour the block's texture is multiplied with.
+pub struct BlockTint {
+    /// Name of the colour map in the texture pack, e.g. `grass`.
+    pub colour_map: String,
+    /// Whether only the top face is tinted, e.g. to leave the dirt of grass blocks untouched.
+    pub is_top_only: bool
+}
+
 /// @brief Struct designed to hold geometric and tangibility data for each individual block.
 pub struct BlockDataHolder {
     pub id: BlockId,
-    pub tex_top_coord: Vector2i,
-    pub tex_side_coord: Vector2i,
-    pub tex_bottom_coord: Vector2i,
+    /// Names of the textures in the block texture atlas.
+    pub tex_top: String,
+    pub tex_side: String,
+    pub tex_bottom: String,
+    pub tint: Option<BlockTint>,
 
     pub mesh_type: BlockMeshType,
     pub shader_type: BlockShaderType,
+    pub transparency: BlockTransparency,
 
     pub is_opaque: bool,
-    pub is_collidable: bool
+    pub is_collidable: bool,
+    /// Collision box relative to the block's origin, a full cube unless the block file says otherwise.
+    pub collision_box: AABB
 }
 
 #[derive(Default)]
@@ -63,8 +89,11 @@ enum DecodingState {
     Id,
     Opaque,
     Collidable,
+    CollisionBox,
     MeshType,
-    ShaderType
+    ShaderType,
+    Transparency,
+    Tint
 }
 
 impl TryFrom<i32> for BlockMeshType {
@@ -92,39 +121,63 @@ impl TryFrom<i32> for BlockShaderType {
     }
 }
 
+impl TryFrom<i32> for BlockTransparency {
+    type Error = ();
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            x if x == BlockTransparency::Opaque as i32 => Ok(BlockTransparency::Opaque),
+            x if x == BlockTransparency::Cutout as i32 => Ok(BlockTransparency::Cutout),
+            x if x == BlockTransparency::Blended as i32 => Ok(BlockTransparency::Blended),
+            _ => Err(())
+        }
+    }
+}
+
 impl Default for BlockDataHolder {
     fn default() -> Self {
         Self {
             id: BlockId::Air,
-            tex_top_coord: Default::default(),
-            tex_side_coord: Default::default(),
-            tex_bottom_coord: Default::default(),
+            tex_top: String::new(),
+            tex_side: String::new(),
+            tex_bottom: String::new(),
+            tint: None,
             mesh_type: BlockMeshType::Cube,
             shader_type: BlockShaderType::Chunk,
+            transparency: BlockTransparency::Opaque,
             is_opaque: false,
-            is_collidable: false
+            is_collidable: false,
+            collision_box: AABB::new(&glm::vec3(1.0, 1.0, 1.0))
         }
     }
 }
 
 impl BlockData {
     pub fn new(file_name: &str) -> Self {
+        Self::load(file_name).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// @brief Parses the block file with the given name.
+    /// @param file_name Name of the file in the block directory, without extension.
+    /// @return What is wrong with the file if it cannot be parsed.
+    pub fn load(file_name: &str) -> Result<Self, String> {
         let mut result = Self::default();
 
         /* BlockData parses through text strings and applies valid attributes.
 
         Textures are applied first, then Block IDs, opacity data, collision data,
-        mesh data, and shader data.
+        mesh data, shader data and transparency data.
 
         Essentially, blocks being constructed by the renderer depend on this
         file data being correctly imported and read by the program.*/
 
         let in_file = BufReader::new(File::open(
             format!("Res/Blocks/{}.block", file_name))
-            .expect(format!("Unable to open block file: {}!", file_name).as_str()));
+            .map_err(|_| format!("Unable to open block file: {}!", file_name))?);
+        let invalid = |line: &str| format!("Invalid line in block file {}: {}", file_name, line);
         let mut state = DecodingState::Vacant;
         for line in in_file.lines() {
-            let line = line.unwrap();
+            let line = line.map_err(|_| format!("Unable to read block file: {}!", file_name))?;
             let line = line.trim();
             if state == DecodingState::Vacant {
                 match line {
@@ -135,47 +188,32 @@ impl BlockData {
                     "Id" => state = DecodingState::Id,
                     "Opaque" => state = DecodingState::Opaque,
                     "Collidable" => state = DecodingState::Collidable,
+                    "CollisionBox" => state = DecodingState::CollisionBox,
                     "MeshType" => state = DecodingState::MeshType,
                     "ShaderType" => state = DecodingState::ShaderType,
+                    "Transparency" => state = DecodingState::Transparency,
+                    "Tint" => state = DecodingState::Tint,
                     _ => {}
                 }
             } else {
                 match state {
                     DecodingState::TexTop => {
-                        let parts: Vec<_> = line.split(' ').collect();
-                        let x = parts[0].parse::<i32>().unwrap();
-                        let y = parts[1].parse::<i32>().unwrap();
-                        result.data.tex_top_coord.x = x;
-                        result.data.tex_top_coord.y = y;
+                        result.data.tex_top = line.to_string();
                     }
                     DecodingState::TexSide => {
-                        let parts: Vec<_> = line.split(' ').collect();
-                        let x = parts[0].parse::<i32>().unwrap();
-                        let y = parts[1].parse::<i32>().unwrap();
-                        result.data.tex_side_coord.x = x;
-                        result.data.tex_side_coord.y = y;
+                        result.data.tex_side = line.to_string();
                     }
                     DecodingState::TexBottom => {
-                        let parts: Vec<_> = line.split(' ').collect();
-                        let x = parts[0].parse::<i32>().unwrap();
-                        let y = parts[1].parse::<i32>().unwrap();
-                        result.data.tex_bottom_coord.x = x;
-                        result.data.tex_bottom_coord.y = y;
+                        result.data.tex_bottom = line.to_string();
                     }
                     DecodingState::TexAll => {
-                        let parts: Vec<_> = line.split(' ').collect();
-                        let x = parts[0].parse::<i32>().unwrap();
-                        let y = parts[1].parse::<i32>().unwrap();
-                        result.data.tex_top_coord.x = x;
-                        result.data.tex_top_coord.y = y;
-                        result.data.tex_side_coord.x = x;
-                        result.data.tex_side_coord.y = y;
-                        result.data.tex_bottom_coord.x = x;
-                        result.data.tex_bottom_coord.y = y;
+                        result.data.tex_top = line.to_string();
+                        result.data.tex_side = line.to_string();
+                        result.data.tex_bottom = line.to_string();
                     }
                     DecodingState::Id => {
-                        let id = line.parse::<i32>().unwrap();
-                        result.data.id = BlockId::try_from(id).unwrap();
+                        let id = line.parse::<i32>().map_err(|_| invalid(line))?;
+                        result.data.id = BlockId::try_from(id).map_err(|_| invalid(line))?;
                     }
                     DecodingState::Opaque => {
                         result.data.is_opaque = if line == "1" { true } else { false }
@@ -183,13 +221,38 @@ impl BlockData {
                     DecodingState::Collidable => {
                         result.data.is_collidable = if line == "1" { true } else { false }
                     }
+                    DecodingState::CollisionBox => {
+                        // Minimum and maximum corner: "minX minY minZ maxX maxY maxZ"
+                        let parts: Vec<f32> = line.split(' ')
+                            .map(|it| it.parse::<f32>())
+                            .collect::<Result<_, _>>()
+                            .map_err(|_| invalid(line))?;
+                        if parts.len() != 6 {
+                            return Err(invalid(line));
+                        }
+                        let min = glm::vec3(parts[0], parts[1], parts[2]);
+                        let max = glm::vec3(parts[3], parts[4], parts[5]);
+                        result.data.collision_box = AABB::new_with_position(&min, &(max - min));
+                    }
                     DecodingState::MeshType => {
-                        let id = line.parse::<i32>().unwrap();
-                        result.data.mesh_type = BlockMeshType::try_from(id).unwrap();
+                        let id = line.parse::<i32>().map_err(|_| invalid(line))?;
+                        result.data.mesh_type = BlockMeshType::try_from(id).map_err(|_| invalid(line))?;
                     }
                     DecodingState::ShaderType => {
-                        let id = line.parse::<i32>().unwrap();
-                        result.data.shader_type = BlockShaderType::try_from(id).unwrap();
+                        let id = line.parse::<i32>().map_err(|_| invalid(line))?;
+                        result.data.shader_type = BlockShaderType::try_from(id).map_err(|_| invalid(line))?;
+                    }
+                    DecodingState::Transparency => {
+                        let id = line.parse::<i32>().map_err(|_| invalid(line))?;
+                        result.data.transparency = BlockTransparency::try_from(id).map_err(|_| invalid(line))?;
+                    }
+                    DecodingState::Tint => {
+                        // Colour map, optionally followed by "top": "grass top"
+                        let mut parts = line.split(' ');
+                        result.data.tint = Some(BlockTint {
+                            colour_map: parts.next().unwrap_or_default().to_string(),
+                            is_top_only: parts.next() == Some("top")
+                        });
                     }
                     _ => {}
                 }
@@ -197,7 +260,7 @@ impl BlockData {
             }
         }
 
-        result
+        Ok(result)
     }
 
     pub fn block_data(&self) -> &BlockDataHolder {
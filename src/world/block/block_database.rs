@@ -14,25 +14,44 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::ptr;
 use std::sync::{Arc, RwLock};
+use gl::types::{GLfloat, GLuint};
+use crate::config::Config;
+use crate::texture::colour_map::ColourMap;
+use crate::texture::texture_array::TextureArray;
 use crate::texture::texture_atlas::TextureAtlas;
+use crate::texture::texture_pack::TexturePack;
 use crate::world::block::block_data::BlockData;
 use crate::world::block::block_id::BlockId;
 use crate::world::block::block_types::block_type::{BlockType, DefaultBlock};
 
 static mut INSTANCE_PTR: *mut BlockDatabase = ptr::null_mut();
 
+/// Tint of blocks that are not tinted, or whose colour map does not exist.
+pub const NO_TINT: [GLfloat; 3] = [1., 1., 1.];
+
 /// @brief Singleton class that determines status and ID of blocks as a whole.
 pub struct BlockDatabase {
     pub texture_atlas: TextureAtlas,
+    /// Used instead of the atlas for chunk meshes when texture arrays are enabled
+    pub texture_array: Option<TextureArray>,
+    colour_maps: BTreeMap<String, ColourMap>,
+    anisotropy: f32,
     blocks: [Box<dyn BlockType>; BlockId::NUM_TYPES]
 }
 
 impl BlockDatabase {
-    fn new() -> Self {
-        let texture_atlas = TextureAtlas::new("DefaultPack");
-        let blocks: [Box<dyn BlockType>; 12] = [
+    fn new(config: &Config) -> Self {
+        let texture_atlas = TextureAtlas::new(&config.texture_pack);
+        let texture_array = if config.texture_arrays {
+            Some(TextureArray::new(&config.texture_pack, config.anisotropy))
+        } else {
+            None
+        };
+        let colour_maps = TexturePack::load_or_default(&config.texture_pack).collect_colour_maps();
+        let blocks: [Box<dyn BlockType>; BlockId::NUM_TYPES] = [
             Box::new(DefaultBlock::new("Air")),
             Box::new(DefaultBlock::new("Grass")),
             Box::new(DefaultBlock::new("Dirt")),
@@ -44,17 +63,31 @@ impl BlockDatabase {
             Box::new(DefaultBlock::new("Cactus")),
             Box::new(DefaultBlock::new("TallGrass")),
             Box::new(DefaultBlock::new("Rose")),
-            Box::new(DefaultBlock::new("DeadShrub"))
+            Box::new(DefaultBlock::new("DeadShrub")),
+            Box::new(DefaultBlock::new("Glass")),
+            Box::new(DefaultBlock::new("CoalOre")),
+            Box::new(DefaultBlock::new("IronOre")),
+            Box::new(DefaultBlock::new("GoldOre")),
+            Box::new(DefaultBlock::new("DiamondOre")),
+            Box::new(DefaultBlock::new("Gravel"))
         ];
-        Self { texture_atlas, blocks }
+        Self {
+            texture_atlas,
+            texture_array,
+            colour_maps,
+            anisotropy: config.anisotropy,
+            blocks
+        }
     }
 
-    pub fn get() -> &'static Self {
+    /// @brief Creates the database with the configured textures, unless it already exists.
+    /// @param config
+    pub fn init(config: &Config) -> &'static Self {
         unsafe {
             if INSTANCE_PTR == ptr::null_mut() {
                 // Allocate the instance on heap memory,
                 // then leak it to get the raw pointer.
-                let instance = Box::new(BlockDatabase::new());
+                let instance = Box::new(BlockDatabase::new(config));
                 INSTANCE_PTR = Box::leak(instance);
             }
 
@@ -62,6 +95,78 @@ impl BlockDatabase {
         }
     }
 
+    /// @brief The database created by `init`, which has to run first so the configured texture
+    /// pack is the one loaded.
+    pub fn get() -> &'static Self {
+        unsafe {
+            if INSTANCE_PTR == ptr::null_mut() {
+                panic!("BlockDatabase::get() called before BlockDatabase::init()");
+            }
+
+            &*INSTANCE_PTR
+        }
+    }
+
+    /// @brief Replaces the block textures with those of another pack.
+    /// No chunk mesh may be built meanwhile, and existing meshes keep the old texture coordinates.
+    /// @param texture_pack
+    pub fn set_texture_pack(texture_pack: &str) {
+        Self::get();
+        unsafe {
+            let instance = &mut *INSTANCE_PTR;
+            instance.texture_atlas = TextureAtlas::new(texture_pack);
+            instance.colour_maps = TexturePack::load_or_default(texture_pack).collect_colour_maps();
+            if instance.texture_array.is_some() {
+                instance.texture_array = Some(TextureArray::new(texture_pack, instance.anisotropy));
+            }
+        }
+    }
+
+    /// @brief Parses every block file again and reloads the textures of the current pack.
+    /// No chunk mesh may be built meanwhile, and existing meshes keep the old data.
+    pub fn reload() {
+        for block in Self::get().blocks.iter() {
+            block.reload();
+        }
+        let texture_pack = Self::get().texture_atlas.pack_id().to_string();
+        Self::set_texture_pack(&texture_pack);
+    }
+
+    /// @brief Binds the textures chunk meshes are drawn with, the atlas regions they look up
+    /// and the table their animations play from.
+    pub fn bind_block_textures(&self) {
+        match self.texture_array.as_ref() {
+            Some(texture_array) => {
+                texture_array.bind_texture();
+                texture_array.bind_animations();
+            }
+            None => {
+                self.texture_atlas.bind_texture();
+                self.texture_atlas.bind_regions();
+                self.texture_atlas.bind_animations();
+            }
+        }
+    }
+
+    /// @brief Index of the named texture a chunk vertex is packed with: its texture array layer,
+    /// or its region in the atlas' region table.
+    /// @param name
+    pub fn face_texture(&self, name: &str) -> GLuint {
+        match self.texture_array.as_ref() {
+            Some(texture_array) => texture_array.get_layer(name) as _,
+            None => self.texture_atlas.get_texture_index(name)
+        }
+    }
+
+    /// @brief Colour a tinted block takes in the given climate.
+    /// @param colour_map Name of the colour map in the texture pack.
+    /// @param temperature From 0, the coldest, to 1, the hottest.
+    /// @param humidity From 0, the driest, to 1, the wettest.
+    pub fn tint(&self, colour_map: &str, temperature: f32, humidity: f32) -> [GLfloat; 3] {
+        self.colour_maps.get(colour_map)
+            .map_or(NO_TINT, |map| map.sample(temperature, humidity))
+    }
+
     pub fn get_block(&self, id: BlockId) -> &dyn BlockType {
         self.blocks[id as usize].as_ref()
     }
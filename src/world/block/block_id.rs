@@ -30,11 +30,49 @@ pub enum BlockId {
     Cactus = 8,
     Rose = 9,
     TallGrass = 10,
-    DeadShrub = 11
+    DeadShrub = 11,
+    Glass = 12,
+    CoalOre = 13,
+    IronOre = 14,
+    GoldOre = 15,
+    DiamondOre = 16,
+    Gravel = 17
 }
 
 impl BlockId {
-    pub const NUM_TYPES: usize = 12;
+    pub const NUM_TYPES: usize = 18;
+
+    /// @brief Block with the given name, the name of its block file.
+    /// @param name
+    pub fn from_name(name: &str) -> Option<Self> {
+        (0..Self::NUM_TYPES as i32)
+            .filter_map(|id| Self::try_from(id).ok())
+            .find(|id| id.name() == name)
+    }
+
+    /// @brief Name of the block file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Air => "Air",
+            Self::Grass => "Grass",
+            Self::Dirt => "Dirt",
+            Self::Stone => "Stone",
+            Self::OakBark => "OakBark",
+            Self::OakLeaf => "OakLeaf",
+            Self::Sand => "Sand",
+            Self::Water => "Water",
+            Self::Cactus => "Cactus",
+            Self::Rose => "Rose",
+            Self::TallGrass => "TallGrass",
+            Self::DeadShrub => "DeadShrub",
+            Self::Glass => "Glass",
+            Self::CoalOre => "CoalOre",
+            Self::IronOre => "IronOre",
+            Self::GoldOre => "GoldOre",
+            Self::DiamondOre => "DiamondOre",
+            Self::Gravel => "Gravel"
+        }
+    }
 }
 
 impl TryFrom<i32> for BlockId {
@@ -54,6 +92,12 @@ impl TryFrom<i32> for BlockId {
             x if x == BlockId::Rose as i32 => Ok(BlockId::Rose),
             x if x == BlockId::TallGrass as i32 => Ok(BlockId::TallGrass),
             x if x == BlockId::DeadShrub as i32 => Ok(BlockId::DeadShrub),
+            x if x == BlockId::Glass as i32 => Ok(BlockId::Glass),
+            x if x == BlockId::CoalOre as i32 => Ok(BlockId::CoalOre),
+            x if x == BlockId::IronOre as i32 => Ok(BlockId::IronOre),
+            x if x == BlockId::GoldOre as i32 => Ok(BlockId::GoldOre),
+            x if x == BlockId::DiamondOre as i32 => Ok(BlockId::DiamondOre),
+            x if x == BlockId::Gravel as i32 => Ok(BlockId::Gravel),
             _ => Err(())
         }
     }
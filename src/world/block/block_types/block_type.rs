@@ -19,16 +19,21 @@ use crate::world::block::block_data::BlockData;
 
 pub trait BlockType {
     fn data(&self) -> Arc<RwLock<BlockData>>;
+
+    /// @brief Parses the block file again, keeping the current data if it is invalid.
+    fn reload(&self);
 }
 
 pub struct DefaultBlock {
-    data: Arc<RwLock<BlockData>>
+    data: Arc<RwLock<BlockData>>,
+    file_name: String
 }
 
 impl DefaultBlock {
     pub fn new(file_name: &str) -> Self {
         Self {
-            data: Arc::new(RwLock::new(BlockData::new(file_name)))
+            data: Arc::new(RwLock::new(BlockData::new(file_name))),
+            file_name: file_name.to_string()
         }
     }
 }
@@ -37,4 +42,11 @@ impl BlockType for DefaultBlock {
     fn data(&self) -> Arc<RwLock<BlockData>> {
         Arc::clone(&self.data)
     }
+
+    fn reload(&self) {
+        match BlockData::load(&self.file_name) {
+            Ok(data) => *self.data.write().unwrap() = data,
+            Err(error) => log::error!("{}", error)
+        }
+    }
 }
\ No newline at end of file
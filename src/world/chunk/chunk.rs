@@ -14,15 +14,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::sync::Arc;
+use nalgebra_glm::IVec3;
 use sfml::system::{Vector2i, Vector3i};
 use crate::camera::Camera;
 use crate::renderer::render_master::RenderMaster;
+use crate::util;
 use crate::util::array2d::Array2D;
 use crate::util::unsafe_cell_wrapper::UnsafeCellWrapper;
 use crate::world::block::block_id::BlockId;
 use crate::world::block::chunk_block::ChunkBlock;
 use crate::world::chunk::chunk_section::ChunkSection;
+use crate::world::generation::biome::biome::Climate;
 use crate::world::generation::terrain_generator::TerrainGenerator;
 use crate::world::world::World;
 use crate::world::world_constants::CHUNK_SIZE;
@@ -36,6 +40,7 @@ pub trait IChunk {
 pub struct Chunk {
     chunks: Vec<ChunkSection>,
     highest_blocks: Array2D<i32>,
+    climate: Array2D<Climate>,
     location: Vector2i,
 
     p_world: Arc<UnsafeCellWrapper<World>>,
@@ -50,6 +55,7 @@ impl Chunk {
         let mut result = Self {
             chunks: Vec::new(),
             highest_blocks: Array2D::new(CHUNK_SIZE),
+            climate: Array2D::new(CHUNK_SIZE),
             location,
             p_world: Arc::clone(&world),
             is_loaded: false,
@@ -69,24 +75,50 @@ impl Chunk {
         false
     }
 
+    /// @brief Whether every section the camera can see has a buffered mesh, so that the column
+    /// can be drawn without holes.
+    /// @param camera
+    pub fn has_visible_meshes(&self, camera: &Camera) -> bool {
+        self.is_loaded && self.chunks.iter().all(|chunk| {
+            chunk.has_buffered() || !camera.get_frustum().is_box_in_frustum(chunk.aabb)
+        })
+    }
+
     pub fn get_height_at(&self, x: i32, z: i32) -> i32 {
         *self.highest_blocks.get(x as _, z as _)
     }
 
-    pub fn draw_chunks(&mut self, renderer: &mut RenderMaster, camera: &Camera) {
+    pub fn get_climate(&self, x: i32, z: i32) -> Climate {
+        *self.climate.get(x as _, z as _)
+    }
+
+    pub fn set_climate(&mut self, x: i32, z: i32, climate: Climate) {
+        *self.climate.get_mut(x as _, z as _) = climate;
+    }
+
+    /// @brief Draws the sections of the column the camera can see.
+    /// @param renderer
+    /// @param camera
+    /// @param visible_sections Sections left after frustum and occlusion culling.
+    pub fn draw_chunks(
+        &mut self,
+        renderer: &mut RenderMaster,
+        camera: &Camera,
+        visible_sections: &HashSet<IVec3>
+    ) {
+        // Meshes are buffered beforehand by `ChunkManager::buffer_meshes`, within the upload budget
         for chunk in self.chunks.iter_mut() {
-            if chunk.has_mesh() {
-                if !chunk.has_buffered() {
-                    chunk.buffer_mesh();
-                }
-
-                if camera.get_frustum().is_box_in_frustum(chunk.aabb) {
-                    renderer.draw_chunk(chunk);
-                }
+            if chunk.has_buffered() && visible_sections.contains(&util::vector3i_to_ivec3(chunk.get_location())) {
+                chunk.sort_translucent_faces(&camera.position);
+                renderer.draw_chunk(chunk);
             }
         }
     }
 
+    pub fn get_section_count(&self) -> i32 {
+        self.chunks.len() as _
+    }
+
     pub fn has_loaded(&self) -> bool {
         self.is_loaded
     }
@@ -0,0 +1,485 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use std::ffi::c_void;
+use std::{mem, ptr};
+use gl::types::{GLfloat, GLint, GLsizei, GLsizeiptr, GLuint};
+use crate::gl::gl_functions;
+
+static mut INSTANCE_PTR: *mut ChunkBufferArena = ptr::null_mut();
+
+/// Integers per vertex: the packed position, corner, light and texture, then the packed tint.
+pub const VERTEX_SIZE: usize = 2;
+
+/// Attribute index and component count of every vertex attribute, in the order they are
+/// interleaved in.
+const ATTRIBUTES: [(GLuint, GLint); 2] = [(0, 1), (1, 1)];
+
+/// Vertices are handed out in pages of this many, each page storing the origin of the mesh
+/// it belongs to. The vertex shader finds it from `gl_VertexID`, which includes the base vertex.
+pub const ORIGIN_PAGE_SIZE: u32 = 64;
+
+/// Texture unit the buffer texture holding the origin of every page is bound to.
+pub const SECTION_ORIGINS_UNIT: GLuint = 1;
+
+/// Vertices and indices the buffers have room for at first. They double whenever they run out.
+const INITIAL_VERTEX_CAPACITY: u32 = 1 << 18;
+const INITIAL_INDEX_CAPACITY: u32 = 1 << 19;
+
+/// @brief Part of the arena's buffers holding the vertices and indices of a single mesh.
+/// Indices count from the mesh's first vertex, which starts a page.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct ArenaRange {
+    pub first_vertex: u32,
+    pub vertex_count: u32,
+    pub first_index: u32,
+    pub index_count: u32
+}
+
+/// @brief Memory use of the arena, and how many draw calls drew how many meshes last frame.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct ArenaStats {
+    pub vertex_capacity: u32,
+    pub vertices_used: u32,
+    pub index_capacity: u32,
+    pub indices_used: u32,
+    pub allocations: u32,
+    pub draw_calls: u32,
+    pub drawn_meshes: u32
+}
+
+/// @brief First-fit allocator of ranges within a buffer, merging ranges again as they are freed.
+struct RangeAllocator {
+    capacity: u32,
+    // Start and length of every free range, ordered by start
+    free_ranges: Vec<(u32, u32)>
+}
+
+/// @brief Singleton holding the vertices and indices of every chunk mesh in a few large
+/// buffers shared by a single VAO, so that a whole pass can be drawn with one call.
+pub struct ChunkBufferArena {
+    vao: GLuint,
+    vertex_buffer: GLuint,
+    index_buffer: GLuint,
+    origin_buffer: GLuint,
+    origin_texture: GLuint,
+
+    pages: RangeAllocator,
+    indices: RangeAllocator,
+
+    allocations: u32,
+    draw_calls: u32,
+    drawn_meshes: u32,
+    last_frame: (u32, u32)
+}
+
+impl RangeAllocator {
+    fn new(capacity: u32) -> Self {
+        Self {
+            capacity,
+            free_ranges: vec![(0, capacity)]
+        }
+    }
+
+    fn allocate(&mut self, count: u32) -> Option<u32> {
+        let index = self.free_ranges.iter().position(|(_, length)| *length >= count)?;
+        let (start, length) = self.free_ranges[index];
+        if length == count {
+            self.free_ranges.remove(index);
+        } else {
+            self.free_ranges[index] = (start + count, length - count);
+        }
+        Some(start)
+    }
+
+    fn free(&mut self, start: u32, count: u32) {
+        let index = self.free_ranges.partition_point(|(free_start, _)| *free_start < start);
+        self.free_ranges.insert(index, (start, count));
+
+        // Merge with the following range, then with the preceding one
+        if index + 1 < self.free_ranges.len() && start + count == self.free_ranges[index + 1].0 {
+            self.free_ranges[index].1 += self.free_ranges.remove(index + 1).1;
+        }
+        if index > 0 {
+            let (previous_start, previous_length) = self.free_ranges[index - 1];
+            if previous_start + previous_length == start {
+                self.free_ranges[index - 1].1 += self.free_ranges.remove(index).1;
+            }
+        }
+    }
+
+    fn grow(&mut self, capacity: u32) {
+        let old_capacity = self.capacity;
+        self.capacity = capacity;
+        self.free(old_capacity, capacity - old_capacity);
+    }
+
+    fn used(&self) -> u32 {
+        self.capacity - self.free_ranges.iter().map(|(_, length)| length).sum::<u32>()
+    }
+}
+
+impl ChunkBufferArena {
+    fn new() -> Self {
+        let pages = INITIAL_VERTEX_CAPACITY / ORIGIN_PAGE_SIZE;
+        let mut result = Self {
+            vao: 0,
+            vertex_buffer: Self::create_buffer(Self::vertex_bytes(INITIAL_VERTEX_CAPACITY)),
+            index_buffer: Self::create_buffer(Self::index_bytes(INITIAL_INDEX_CAPACITY)),
+            origin_buffer: Self::create_buffer(Self::origin_bytes(pages)),
+            origin_texture: 0,
+            pages: RangeAllocator::new(pages),
+            indices: RangeAllocator::new(INITIAL_INDEX_CAPACITY),
+            allocations: 0,
+            draw_calls: 0,
+            drawn_meshes: 0,
+            last_frame: (0, 0)
+        };
+        unsafe {
+            gl::GenVertexArrays(1, &mut result.vao);
+            gl::GenTextures(1, &mut result.origin_texture);
+        }
+        result.set_up_vao();
+        result.attach_origins();
+        result
+    }
+
+    /// @brief Creates the arena unless it already exists. Needs a current GL context.
+    pub fn get() -> &'static Self {
+        unsafe {
+            if INSTANCE_PTR == ptr::null_mut() {
+                // Allocate the instance on heap memory,
+                // then leak it to get the raw pointer.
+                let instance = Box::new(ChunkBufferArena::new());
+                INSTANCE_PTR = Box::leak(instance);
+            }
+
+            &*INSTANCE_PTR
+        }
+    }
+
+    /// @brief Copies the data of a mesh into the arena, growing the buffers if they are full.
+    /// @param vertices Packed vertices, `VERTEX_SIZE` integers each.
+    /// @param indices
+    /// @param origin World position the vertex positions are relative to.
+    pub fn allocate(vertices: &[GLuint], indices: &[GLuint], origin: &glm::TVec3<f32>) -> ArenaRange {
+        Self::get();
+        let instance = unsafe { &mut *INSTANCE_PTR };
+
+        let vertex_count = (vertices.len() / VERTEX_SIZE) as u32;
+        let index_count = indices.len() as u32;
+        let page_count = Self::page_count(vertex_count);
+        let first_page = match instance.pages.allocate(page_count) {
+            Some(first) => first,
+            None => {
+                instance.grow_pages(page_count);
+                instance.pages.allocate(page_count).unwrap()
+            }
+        };
+        let first_vertex = first_page * ORIGIN_PAGE_SIZE;
+        let first_index = match instance.indices.allocate(index_count) {
+            Some(first) => first,
+            None => {
+                instance.grow_indices(index_count);
+                instance.indices.allocate(index_count).unwrap()
+            }
+        };
+
+        let range = ArenaRange { first_vertex, vertex_count, first_index, index_count };
+        Self::upload(instance.vertex_buffer, Self::vertex_bytes(first_vertex), vertices);
+        Self::upload(instance.index_buffer, Self::index_bytes(first_index), indices);
+        let origins: Vec<GLfloat> = (0..page_count)
+            .flat_map(|_| [origin.x, origin.y, origin.z, 0.])
+            .collect();
+        Self::upload(instance.origin_buffer, Self::origin_bytes(first_page), &origins);
+        instance.allocations += 1;
+        range
+    }
+
+    /// @brief Gives the space of a mesh back to the arena.
+    /// @param range
+    pub fn free(range: &ArenaRange) {
+        Self::get();
+        let instance = unsafe { &mut *INSTANCE_PTR };
+        instance.pages.free(range.first_vertex / ORIGIN_PAGE_SIZE, Self::page_count(range.vertex_count));
+        instance.indices.free(range.first_index, range.index_count);
+        instance.allocations -= 1;
+    }
+
+    /// @brief Replaces the indices of a mesh, e.g. to draw its faces in a different order.
+    /// Their count must not change.
+    /// @param range
+    /// @param indices
+    pub fn update_indices(range: &ArenaRange, indices: &[GLuint]) {
+        debug_assert_eq!(indices.len(), range.index_count as usize);
+        Self::upload(Self::get().index_buffer, Self::index_bytes(range.first_index), indices);
+    }
+
+    /// @brief Draws the given meshes with a single call, in order.
+    /// @param ranges
+    pub fn draw(ranges: &[ArenaRange]) {
+        if ranges.is_empty() {
+            return;
+        }
+        Self::get();
+        let instance = unsafe { &mut *INSTANCE_PTR };
+
+        let counts: Vec<GLsizei> = ranges.iter().map(|range| range.index_count as _).collect();
+        let offsets: Vec<*const c_void> = ranges.iter()
+            .map(|range| Self::index_bytes(range.first_index) as *const c_void)
+            .collect();
+        let base_vertices: Vec<GLint> = ranges.iter().map(|range| range.first_vertex as _).collect();
+
+        gl_functions::bind_vao(instance.vao);
+        unsafe {
+            gl::ActiveTexture(gl::TEXTURE0 + SECTION_ORIGINS_UNIT);
+            gl::BindTexture(gl::TEXTURE_BUFFER, instance.origin_texture);
+            gl::ActiveTexture(gl::TEXTURE0);
+        }
+        gl_functions::multi_draw_elements(&counts, &offsets, &base_vertices);
+        instance.draw_calls += 1;
+        instance.drawn_meshes += ranges.len() as u32;
+    }
+
+    /// @brief Starts counting the draw calls of a new frame.
+    pub fn begin_frame() {
+        Self::get();
+        let instance = unsafe { &mut *INSTANCE_PTR };
+        instance.last_frame = (instance.draw_calls, instance.drawn_meshes);
+        instance.draw_calls = 0;
+        instance.drawn_meshes = 0;
+    }
+
+    pub fn stats(&self) -> ArenaStats {
+        ArenaStats {
+            vertex_capacity: self.pages.capacity * ORIGIN_PAGE_SIZE,
+            vertices_used: self.pages.used() * ORIGIN_PAGE_SIZE,
+            index_capacity: self.indices.capacity,
+            indices_used: self.indices.used(),
+            allocations: self.allocations,
+            draw_calls: self.last_frame.0,
+            drawn_meshes: self.last_frame.1
+        }
+    }
+
+    fn grow_pages(&mut self, count: u32) {
+        let capacity = Self::grown_capacity(self.pages.capacity, count);
+        self.vertex_buffer = Self::copy_to_new_buffer(
+            self.vertex_buffer,
+            Self::vertex_bytes(self.pages.capacity * ORIGIN_PAGE_SIZE),
+            Self::vertex_bytes(capacity * ORIGIN_PAGE_SIZE)
+        );
+        self.origin_buffer = Self::copy_to_new_buffer(
+            self.origin_buffer,
+            Self::origin_bytes(self.pages.capacity),
+            Self::origin_bytes(capacity)
+        );
+        self.pages.grow(capacity);
+        self.set_up_vao();
+        self.attach_origins();
+        log::debug!("Chunk vertex buffer grown to {} vertices", capacity * ORIGIN_PAGE_SIZE);
+    }
+
+    fn grow_indices(&mut self, count: u32) {
+        let capacity = Self::grown_capacity(self.indices.capacity, count);
+        self.index_buffer = Self::copy_to_new_buffer(
+            self.index_buffer,
+            Self::index_bytes(self.indices.capacity),
+            Self::index_bytes(capacity)
+        );
+        self.indices.grow(capacity);
+        self.set_up_vao();
+        log::debug!("Chunk index buffer grown to {} indices", capacity);
+    }
+
+    /// @brief Doubles the capacity until a range of the given size is sure to fit at the end.
+    fn grown_capacity(capacity: u32, count: u32) -> u32 {
+        let mut result = capacity * 2;
+        while result - capacity < count {
+            result *= 2;
+        }
+        result
+    }
+
+    /// @brief Pages needed to hold the given number of vertices.
+    fn page_count(vertices: u32) -> u32 {
+        (vertices + ORIGIN_PAGE_SIZE - 1) / ORIGIN_PAGE_SIZE
+    }
+
+    /// @brief Points the VAO at the current buffers, which changes every time one grows.
+    fn set_up_vao(&self) {
+        let stride = (VERTEX_SIZE * mem::size_of::<GLuint>()) as GLsizei;
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vertex_buffer);
+
+            let mut offset = 0;
+            for (index, components) in ATTRIBUTES {
+                // Integer attributes, which would otherwise be converted to floats
+                gl::VertexAttribIPointer(
+                    index,
+                    components,
+                    gl::UNSIGNED_INT,
+                    stride,
+                    (offset * mem::size_of::<GLuint>()) as *const c_void
+                );
+                gl::EnableVertexAttribArray(index);
+                offset += components as usize;
+            }
+
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.index_buffer);
+            gl::BindVertexArray(0);
+        }
+    }
+
+    /// @brief Points the origin texture at the current origin buffer, which changes every time it grows.
+    fn attach_origins(&self) {
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_BUFFER, self.origin_texture);
+            gl::TexBuffer(gl::TEXTURE_BUFFER, gl::RGBA32F, self.origin_buffer);
+            gl::BindTexture(gl::TEXTURE_BUFFER, 0);
+        }
+    }
+
+    fn create_buffer(size: GLsizeiptr) -> GLuint {
+        let mut buffer: GLuint = 0;
+        unsafe {
+            gl::GenBuffers(1, &mut buffer);
+            // Not bound to the element array target, which would change whichever VAO is bound
+            gl::BindBuffer(gl::COPY_WRITE_BUFFER, buffer);
+            gl::BufferData(gl::COPY_WRITE_BUFFER, size, ptr::null(), gl::DYNAMIC_DRAW);
+        }
+        buffer
+    }
+
+    fn copy_to_new_buffer(buffer: GLuint, size: GLsizeiptr, new_size: GLsizeiptr) -> GLuint {
+        let new_buffer = Self::create_buffer(new_size);
+        unsafe {
+            gl::BindBuffer(gl::COPY_READ_BUFFER, buffer);
+            gl::CopyBufferSubData(gl::COPY_READ_BUFFER, gl::COPY_WRITE_BUFFER, 0, 0, size);
+            gl::DeleteBuffers(1, &buffer);
+        }
+        new_buffer
+    }
+
+    fn upload<T>(buffer: GLuint, offset: GLsizeiptr, data: &[T]) {
+        unsafe {
+            gl::BindBuffer(gl::COPY_WRITE_BUFFER, buffer);
+            gl::BufferSubData(gl::COPY_WRITE_BUFFER, offset, mem::size_of_val(data) as _, data.as_ptr() as _);
+        }
+    }
+
+    /// @brief Size of the given number of vertices in bytes.
+    pub fn vertex_bytes(vertices: u32) -> GLsizeiptr {
+        (vertices as usize * VERTEX_SIZE * mem::size_of::<GLuint>()) as _
+    }
+
+    /// @brief Size of the origins of the given number of pages in bytes.
+    fn origin_bytes(pages: u32) -> GLsizeiptr {
+        (pages as usize * 4 * mem::size_of::<GLfloat>()) as _
+    }
+
+    /// @brief Size of the given number of indices in bytes.
+    pub fn index_bytes(indices: u32) -> GLsizeiptr {
+        (indices as usize * mem::size_of::<GLuint>()) as _
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allocator_with(capacity: u32, allocations: &[u32]) -> (RangeAllocator, Vec<u32>) {
+        let mut allocator = RangeAllocator::new(capacity);
+        let starts = allocations.iter()
+            .map(|count| allocator.allocate(*count).unwrap())
+            .collect();
+        (allocator, starts)
+    }
+
+    #[test]
+    fn exact_fit_removes_the_free_range() {
+        let mut allocator = RangeAllocator::new(16);
+
+        assert_eq!(allocator.allocate(16), Some(0));
+        assert!(allocator.free_ranges.is_empty());
+        assert_eq!(allocator.used(), 16);
+        assert_eq!(allocator.allocate(1), None);
+    }
+
+    #[test]
+    fn smaller_allocation_splits_the_free_range() {
+        let (allocator, starts) = allocator_with(16, &[4, 6]);
+
+        assert_eq!(starts, vec![0, 4]);
+        assert_eq!(allocator.free_ranges, vec![(10, 6)]);
+        assert_eq!(allocator.used(), 10);
+    }
+
+    #[test]
+    fn free_merges_with_the_range_on_the_left() {
+        let (mut allocator, _) = allocator_with(16, &[4, 4, 4]);
+        allocator.free(0, 4);
+        allocator.free(4, 4);
+
+        assert_eq!(allocator.free_ranges, vec![(0, 8), (12, 4)]);
+    }
+
+    #[test]
+    fn free_merges_with_the_range_on_the_right() {
+        let (mut allocator, _) = allocator_with(16, &[4, 4, 4]);
+        allocator.free(4, 4);
+
+        assert_eq!(allocator.free_ranges, vec![(4, 4), (12, 4)]);
+        allocator.free(8, 4);
+        assert_eq!(allocator.free_ranges, vec![(4, 12)]);
+    }
+
+    #[test]
+    fn free_merges_with_the_ranges_on_both_sides() {
+        let (mut allocator, _) = allocator_with(16, &[4, 4, 4, 4]);
+        allocator.free(0, 4);
+        allocator.free(8, 4);
+        allocator.free(4, 4);
+
+        assert_eq!(allocator.free_ranges, vec![(0, 12)]);
+        assert_eq!(allocator.used(), 4);
+    }
+
+    #[test]
+    fn freed_range_is_reused_first() {
+        let (mut allocator, _) = allocator_with(16, &[4, 4, 4]);
+        allocator.free(4, 4);
+
+        assert_eq!(allocator.allocate(3), Some(4));
+        assert_eq!(allocator.allocate(4), Some(12));
+        assert_eq!(allocator.free_ranges, vec![(7, 1)]);
+    }
+
+    #[test]
+    fn grows_when_no_range_fits() {
+        let (mut allocator, _) = allocator_with(16, &[12]);
+        assert_eq!(allocator.allocate(8), None);
+
+        allocator.grow(ChunkBufferArena::grown_capacity(allocator.capacity, 8));
+
+        assert_eq!(allocator.capacity, 32);
+        assert_eq!(allocator.free_ranges, vec![(12, 20)]);
+        assert_eq!(allocator.allocate(8), Some(12));
+    }
+}
@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use gl::types::GLfloat;
+use sfml::system::Vector3i;
+use crate::maths::vector2xz::VectorXZ;
+use crate::physics::aabb::AABB;
+use crate::world::block::block_data::BlockDataHolder;
+use crate::world::block::block_database::{BlockDatabase, NO_TINT};
+use crate::world::block::block_id::BlockId;
+use crate::world::chunk::chunk_mesh::ChunkMeshCollection;
+use crate::world::chunk::chunk_mesh_builder::{BACK_FACE, FRONT_FACE, LEFT_FACE, LIGHT_TOP, LIGHT_X, LIGHT_Z, RIGHT_FACE, TOP_FACE};
+use crate::world::chunk::chunk_surface::{ChunkSurface, SurfaceColumn};
+use crate::world::generation::biome::biome::Climate;
+use crate::world::world_constants::{CHUNK_SIZE, WATER_LEVEL};
+
+/// Cell size in blocks of the coarsest level of detail.
+pub const MAX_LOD_STEP: i32 = 8;
+
+/// @brief Cell size in blocks a column is drawn with, which doubles every time the distance
+/// to the camera does.
+/// @param distance In chunks from the camera's chunk.
+/// @param render_distance In chunks, up to which columns are drawn at full detail.
+pub fn lod_step(distance: i32, render_distance: i32) -> i32 {
+    let mut step = 2;
+    let mut band_end = render_distance.max(1) * 2;
+    while distance > band_end && step < MAX_LOD_STEP {
+        step *= 2;
+        band_end *= 2;
+    }
+    step
+}
+
+/// @brief Coarse mesh of a distant chunk column, made of cells of `step` by `step` blocks
+/// which each take the height and top block of their highest column.
+pub struct ChunkLod {
+    meshes: ChunkMeshCollection,
+    pub aabb: AABB,
+    location: VectorXZ,
+    step: i32
+}
+
+impl ChunkLod {
+    /// @brief Builds the meshes of a column from its surface. The outer walls of the column
+    /// hang below its lowest cell, so that no gaps open up next to columns drawn with
+    /// another level of detail.
+    /// @param surface
+    /// @param step Cell size in blocks, a divisor of the chunk size.
+    pub fn new(surface: &ChunkSurface, step: i32) -> Self {
+        let location = surface.get_location();
+        let cells = CHUNK_SIZE as i32 / step;
+        let tops: Vec<SurfaceColumn> = (0..cells * cells)
+            .map(|i| Self::cell_top(surface, i / cells * step, i % cells * step, step))
+            .collect();
+
+        // Vertex positions are packed as unsigned numbers, the skirts cannot reach below the world
+        let skirt_bottom = (surface.get_min_height() - step).max(0);
+        let get_height = |x: i32, z: i32| {
+            if x < 0 || z < 0 || x >= cells || z >= cells {
+                skirt_bottom
+            } else {
+                tops[(x * cells + z) as usize].height
+            }
+        };
+
+        let mut meshes = ChunkMeshCollection::default();
+        let chunk_position = Vector3i::new(location.x, 0, location.y);
+        let size = step as GLfloat;
+        for x in 0..cells {
+            for z in 0..cells {
+                let top = &tops[(x * cells + z) as usize];
+                let data = top.block.get_data();
+                let data = data.read().unwrap();
+                let data = data.block_data();
+                let (top_tint, tint) = Self::block_tints(data, &top.climate);
+
+                let texture = BlockDatabase::get().face_texture(&data.tex_top);
+                meshes.solid_mesh.add_face(
+                    Self::scale_face(TOP_FACE, [size, 1., size]),
+                    texture,
+                    &chunk_position,
+                    &Vector3i::new(x * step, top.height, z * step),
+                    LIGHT_TOP,
+                    top_tint
+                );
+
+                let texture = BlockDatabase::get().face_texture(&data.tex_side);
+                let sides = [
+                    (LEFT_FACE, -1, 0, LIGHT_X),
+                    (RIGHT_FACE, 1, 0, LIGHT_X),
+                    (FRONT_FACE, 0, 1, LIGHT_Z),
+                    (BACK_FACE, 0, -1, LIGHT_Z)
+                ];
+                for (face, dx, dz, light) in sides {
+                    // The neighbouring cell draws the wall when it is the higher one
+                    let bottom = get_height(x + dx, z + dz) + 1;
+                    if bottom > top.height {
+                        continue;
+                    }
+                    meshes.solid_mesh.add_face(
+                        Self::scale_face(face, [size, (top.height + 1 - bottom) as _, size]),
+                        texture,
+                        &chunk_position,
+                        &Vector3i::new(x * step, bottom, z * step),
+                        light,
+                        tint
+                    );
+                }
+
+                if top.height < WATER_LEVEL as i32 {
+                    Self::add_water(&mut meshes, top, &chunk_position, x * step, z * step, size);
+                }
+            }
+        }
+
+        let bottom = skirt_bottom as f32;
+        let height = (surface.get_max_height().max(WATER_LEVEL as i32) + 1) as f32 - bottom;
+        Self {
+            meshes,
+            aabb: AABB::new_with_position(
+                &glm::vec3((location.x * CHUNK_SIZE as i32) as _, bottom, (location.y * CHUNK_SIZE as i32) as _),
+                &glm::vec3(CHUNK_SIZE as _, height, CHUNK_SIZE as _)
+            ),
+            location: VectorXZ::new(location.x, location.y),
+            step
+        }
+    }
+
+    pub fn get_location(&self) -> VectorXZ {
+        self.location
+    }
+
+    pub fn get_step(&self) -> i32 {
+        self.step
+    }
+
+    pub fn buffer_mesh(&mut self) {
+        self.meshes.solid_mesh.buffer_mesh();
+        self.meshes.water_mesh.buffer_mesh();
+    }
+
+    /// @brief Size of the mesh waiting to be buffered.
+    pub fn get_pending_bytes(&self) -> usize {
+        self.meshes.solid_mesh.get_pending_bytes() + self.meshes.water_mesh.get_pending_bytes()
+    }
+
+    /// @brief Keeps the water faces ordered back-to-front as seen from the camera.
+    /// @param camera_position
+    pub fn sort_translucent_faces(&mut self, camera_position: &glm::TVec3<f32>) {
+        self.meshes.water_mesh.sort_faces(camera_position);
+    }
+
+    pub fn get_meshes(&self) -> &ChunkMeshCollection {
+        &self.meshes
+    }
+
+    fn add_water(
+        meshes: &mut ChunkMeshCollection,
+        top: &SurfaceColumn,
+        chunk_position: &Vector3i,
+        x: i32,
+        z: i32,
+        size: GLfloat
+    ) {
+        let data = BlockDatabase::get().get_data(BlockId::Water);
+        let data = data.read().unwrap();
+        let data = data.block_data();
+        let (tint, _) = Self::block_tints(data, &top.climate);
+        let texture = BlockDatabase::get().face_texture(&data.tex_top);
+
+        meshes.water_mesh.add_face(
+            Self::scale_face(TOP_FACE, [size, 1., size]),
+            texture,
+            chunk_position,
+            &Vector3i::new(x, WATER_LEVEL as _, z),
+            LIGHT_TOP,
+            tint
+        );
+    }
+
+    /// @brief Highest column of the cell starting at the given column.
+    fn cell_top(surface: &ChunkSurface, x: i32, z: i32, step: i32) -> SurfaceColumn {
+        (0..step * step)
+            .map(|i| *surface.get_column(x + i / step, z + i % step))
+            .max_by_key(|column| column.height)
+            .unwrap()
+    }
+
+    /// @brief Tint of the top face and of the other faces of a block, without blending the
+    /// climate with the columns around it.
+    fn block_tints(data: &BlockDataHolder, climate: &Climate) -> ([GLfloat; 3], [GLfloat; 3]) {
+        let Some(tint) = data.tint.as_ref() else {
+            return (NO_TINT, NO_TINT);
+        };
+
+        let colour = BlockDatabase::get().tint(
+            &tint.colour_map,
+            climate.temperature as f32 / 255.0,
+            climate.humidity as f32 / 255.0
+        );
+        (colour, if tint.is_top_only { NO_TINT } else { colour })
+    }
+
+    /// @brief Stretches a unit block face to the given size along each axis.
+    fn scale_face(face: [GLfloat; 12], size: [GLfloat; 3]) -> [GLfloat; 12] {
+        let mut result = face;
+        for (i, value) in result.iter_mut().enumerate() {
+            *value *= size[i % 3];
+        }
+        result
+    }
+}
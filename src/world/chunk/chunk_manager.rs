@@ -14,32 +14,50 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+extern crate nalgebra_glm as glm;
+
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use sfml::system::Vector2i;
 use crate::camera::Camera;
 use crate::maths::vector2xz::VectorXZ;
+use crate::physics::aabb::AABB;
+use crate::util;
 use crate::util::unsafe_cell_wrapper::UnsafeCellWrapper;
 use crate::world::chunk::chunk::Chunk;
-use crate::world::generation::classic_over_world_generator::ClassicOverWorldGenerator;
+use crate::world::chunk::chunk_lod::ChunkLod;
+use crate::world::chunk::mesh_upload_queue::{PendingSection, UploadBudget, UploadStats, UploadTracker};
+use crate::world::chunk::section_visibility::{SectionFace, SectionVisibility};
 use crate::world::generation::terrain_generator::TerrainGenerator;
 use crate::world::world::World;
+use crate::world::world_constants::CHUNK_SIZE;
 
 pub type ChunkMap = HashMap<VectorXZ, Chunk>;
+pub type LodMap = HashMap<VectorXZ, ChunkLod>;
 
 /// @brief Dynamic chunk manager that affects chunk and block placement.
 pub struct ChunkManager {
     chunks: ChunkMap,
+    lods: LodMap,
+    // Made by the load thread, moved into `lods` once the main thread has buffered them
+    built_lods: VecDeque<ChunkLod>,
+    upload_stats: UploadStats,
     terrain_generator: Box<dyn TerrainGenerator + Send>,
 
     world: Arc<UnsafeCellWrapper<World>>
 }
 
 impl ChunkManager {
-    pub fn new(world: Arc<UnsafeCellWrapper<World>>) -> Self {
+    pub fn new(
+        world: Arc<UnsafeCellWrapper<World>>,
+        terrain_generator: Box<dyn TerrainGenerator + Send>
+    ) -> Self {
         Self {
             chunks: HashMap::new(),
-            terrain_generator: Box::new(ClassicOverWorldGenerator::new()),
+            lods: HashMap::new(),
+            built_lods: VecDeque::new(),
+            upload_stats: UploadStats::default(),
+            terrain_generator,
             world
         }
     }
@@ -82,6 +100,140 @@ impl ChunkManager {
         self.get_chunk_mut(x, z).make_mesh(camera)
     }
 
+    /// @brief Builds the level-of-detail mesh of a column straight from the terrain generator,
+    /// unless it is already there with the given cell size.
+    /// @param x
+    /// @param z
+    /// @param step Cell size in blocks.
+    /// @return Whether a mesh was built.
+    pub fn make_lod(&mut self, x: i32, z: i32, step: i32) -> bool {
+        let key = VectorXZ::new(x, z);
+        let is_made = self.lods.get(&key).is_some_and(|lod| lod.get_step() == step) ||
+            self.built_lods.iter().any(|lod| lod.get_location() == key && lod.get_step() == step);
+        if is_made {
+            return false;
+        }
+
+        let surface = self.terrain_generator.generate_surface_for(Vector2i::new(x, z));
+        self.built_lods.push_back(ChunkLod::new(&surface, step));
+        true
+    }
+
+    /// @brief Buffers the sections meshed since they were last buffered, those the camera can
+    /// see first and then the closest ones, followed by the level-of-detail meshes made since,
+    /// which take the place of the ones they replace. Whatever does not fit in the budget waits
+    /// for the next frame. Has to run on the thread owning the GL context.
+    /// @param camera
+    /// @param visible_sections Sections left after frustum and occlusion culling.
+    /// @param budget
+    pub fn buffer_meshes(
+        &mut self,
+        camera: &Camera,
+        visible_sections: &HashSet<glm::IVec3>,
+        budget: UploadBudget
+    ) {
+        let mut queue = BinaryHeap::new();
+        for (key, chunk) in self.chunks.iter() {
+            for index in 0..chunk.get_section_count() {
+                let section = chunk.get_section(index);
+                if section.is_mesh_pending() {
+                    queue.push(PendingSection::new(
+                        *key,
+                        index,
+                        visible_sections.contains(&util::vector3i_to_ivec3(section.get_location())),
+                        glm::distance2(&section.aabb.centre(), &camera.position)
+                    ));
+                }
+            }
+        }
+
+        let mut tracker = UploadTracker::new(budget);
+        while tracker.can_upload() {
+            let Some(pending) = queue.pop() else {
+                break;
+            };
+            let section = self.chunks.get_mut(&pending.column).unwrap().get_section_mut(pending.index);
+            tracker.add_upload(section.get_pending_bytes());
+            section.buffer_mesh();
+        }
+        while tracker.can_upload() {
+            let Some(mut lod) = self.built_lods.pop_front() else {
+                break;
+            };
+            tracker.add_upload(lod.get_pending_bytes());
+            lod.buffer_mesh();
+            self.lods.insert(lod.get_location(), lod);
+        }
+
+        self.upload_stats = tracker.finish(queue.len(), self.built_lods.len());
+    }
+
+    /// @brief Meshes uploaded by the last `buffer_meshes`, and those left waiting.
+    pub fn get_upload_stats(&self) -> UploadStats {
+        self.upload_stats
+    }
+
+    /// @brief Full-detail chunks and level-of-detail meshes, to draw one in place of the other.
+    pub fn get_chunks_and_lods_mut(&mut self) -> (&mut ChunkMap, &mut LodMap) {
+        (&mut self.chunks, &mut self.lods)
+    }
+
+    /// @brief Sections the camera can see, found by walking outwards from the camera's section.
+    /// A section is only left through a face that the face it was entered through can see,
+    /// never back towards the camera, and only into sections inside the view frustum, so
+    /// that caves behind solid rock and the underside of the world are skipped.
+    /// @param camera
+    /// @param render_distance In chunks.
+    pub fn find_visible_sections(&self, camera: &Camera, render_distance: i32) -> HashSet<glm::IVec3> {
+        let start = glm::IVec3::new(
+            (camera.position.x / CHUNK_SIZE as f32).floor() as _,
+            (camera.position.y / CHUNK_SIZE as f32).floor() as _,
+            (camera.position.z / CHUNK_SIZE as f32).floor() as _
+        );
+        // Sections above the highest column are air, which can still be looked through
+        let top = self.chunks.values()
+            .map(|chunk| chunk.get_section_count())
+            .max()
+            .unwrap_or(0)
+            .max(start.y);
+
+        let mut visible_sections = HashSet::from([start]);
+        // Section, face it was entered through and the directions walked to reach it
+        let mut queue: VecDeque<(glm::IVec3, Option<SectionFace>, u8)> = VecDeque::from([(start, None, 0)]);
+        while let Some((position, entry, directions)) = queue.pop_front() {
+            let visibility = self.get_visibility(&position);
+            for face in SectionFace::ALL {
+                if directions & (1 << face.opposite() as u8) != 0 {
+                    continue;
+                }
+                if entry.is_some_and(|entry| !visibility.is_connected(entry, face)) {
+                    continue;
+                }
+
+                let next = position + face.offset();
+                if next.y < 0 || next.y > top ||
+                    (next.x - start.x).abs() > render_distance ||
+                    (next.z - start.z).abs() > render_distance ||
+                    visible_sections.contains(&next) {
+                    continue;
+                }
+
+                let corner = glm::vec3(next.x as f32, next.y as f32, next.z as f32) * CHUNK_SIZE as f32;
+                let aabb = AABB::new_with_position(
+                    &corner,
+                    &glm::vec3(CHUNK_SIZE as _, CHUNK_SIZE as _, CHUNK_SIZE as _)
+                );
+                if !camera.get_frustum().is_box_in_frustum(aabb) {
+                    continue;
+                }
+
+                visible_sections.insert(next);
+                queue.push_back((next, Some(face.opposite()), directions | (1 << face as u8)));
+            }
+        }
+        visible_sections
+    }
+
     pub fn chunk_loaded_at(&self, x: i32, z: i32) -> bool {
         if !self.chunk_exists_at(x, z) {
             return false;
@@ -113,6 +265,15 @@ impl ChunkManager {
         for chunk in self.chunks.iter_mut() {
             chunk.1.delete_meshes();
         }
+        self.lods.clear();
+        self.built_lods.clear();
+    }
+
+    fn get_visibility(&self, position: &glm::IVec3) -> SectionVisibility {
+        match self.chunks.get(&VectorXZ::new(position.x, position.z)) {
+            Some(chunk) => chunk.get_section(position.y).get_visibility(),
+            None => SectionVisibility::all()
+        }
     }
 
     pub fn get_terrain_generator(&self) -> &dyn TerrainGenerator {
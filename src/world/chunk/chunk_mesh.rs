@@ -14,95 +14,204 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+extern crate nalgebra_glm as glm;
+
+use std::cmp::Ordering;
+use std::mem;
 use gl::types::{GLfloat, GLuint};
 use sfml::system::Vector3i;
-use crate::mesh::Mesh;
-use crate::model::Model;
+use crate::world::chunk::chunk_buffer_arena::{ArenaRange, ChunkBufferArena};
 use crate::world::world_constants::CHUNK_SIZE;
 
-#[derive(Clone, Default)]
+/// Distance the camera has to move before blended faces are sorted again.
+const RESORT_DISTANCE: f32 = 1.0;
+
+/// Bit offsets of the fields packed into the first integer of a vertex, see ChunkVertex.glsl.
+const Y_SHIFT: u32 = 5;
+const Z_SHIFT: u32 = 14;
+const CORNER_SHIFT: u32 = 19;
+const LIGHT_SHIFT: u32 = 21;
+const TEXTURE_SHIFT: u32 = 23;
+
+/// Largest texture index the packed format has room for.
+pub const MAX_TEXTURE_INDEX: GLuint = (1 << (32 - TEXTURE_SHIFT)) - 1;
+
+#[derive(Default)]
 pub struct ChunkMesh {
     pub faces: i32,
 
-    mesh: Mesh,
-    model: Model,
-    light: Vec<GLfloat>,
-    index_index: GLuint
+    vertices: Vec<GLuint>,
+    indices: Vec<GLuint>,
+    origin: glm::TVec3<f32>,
+    range: Option<ArenaRange>,
+    index_index: GLuint,
+
+    // Only kept for blended meshes, whose faces have to be drawn back-to-front
+    is_sorted: bool,
+    face_centres: Vec<glm::TVec3<f32>>,
+    buffered_face_centres: Vec<glm::TVec3<f32>>,
+    last_sort_position: Option<glm::TVec3<f32>>
 }
 
-#[derive(Clone, Default)]
 pub struct ChunkMeshCollection {
     pub solid_mesh: ChunkMesh,
     pub water_mesh: ChunkMesh,
-    pub flora_mesh: ChunkMesh
+    pub flora_mesh: ChunkMesh,
+    pub translucent_mesh: ChunkMesh
 }
 
 impl ChunkMesh {
+    /// @brief Mesh for blended faces, which keeps the face centres around so that
+    /// `sort_faces` can order them back-to-front.
+    pub fn new_sorted() -> Self {
+        Self {
+            is_sorted: true,
+            ..Default::default()
+        }
+    }
+
+    /// @brief Adds a quad, packing each of its vertices into two integers.
+    /// All faces of a mesh have to share the chunk position, which becomes the mesh's origin.
+    /// @param block_face Corners of the face relative to the block, in whole blocks.
+    /// @param texture Index returned by `BlockDatabase::face_texture`.
+    /// @param chunk_position
+    /// @param block_position Relative to the chunk, within 0..=16 horizontally and 0..=511 vertically.
+    /// @param cardinal_light One of 1.0, 0.8, 0.6 and 0.4.
+    /// @param tint
     pub fn add_face(
         &mut self,
         block_face: [GLfloat; 12],
-        texture_coords: [GLfloat; 8],
+        texture: GLuint,
         chunk_position: &Vector3i,
         block_position: &Vector3i,
-        cardinal_light: GLfloat
+        cardinal_light: GLfloat,
+        tint: [GLfloat; 3]
     ) {
+        // Anything larger would spill into the other fields, the texture lookups never go past it
+        let texture = texture.min(MAX_TEXTURE_INDEX);
         self.faces += 1;
-        let vertices = &mut self.mesh.vertex_positions;
-        let tex_coords = &mut self.mesh.texture_coords;
-        let indices = &mut self.mesh.indices;
-
-        texture_coords.iter().for_each(|it| {
-            tex_coords.push(*it);
-        });
+        self.origin = glm::vec3(
+            (chunk_position.x * CHUNK_SIZE as i32) as f32,
+            (chunk_position.y * CHUNK_SIZE as i32) as f32,
+            (chunk_position.z * CHUNK_SIZE as i32) as f32
+        );
+
+        // The light levels are 0.2 apart, counting down from full light
+        let light = ((1.0 - cardinal_light) / 0.2).round() as GLuint & 3;
+        let tint = tint.iter().enumerate()
+            .map(|(channel, value)| ((value.clamp(0., 1.) * 255.).round() as GLuint) << (channel * 8))
+            .sum::<GLuint>();
 
         // Vertex: The current vertex in the "blockFace" vector, 4 vertex in total
         // hence "< 4" Index: X, Y, Z
-        let mut index = 0;
-        for _ in 0..4 {
-            vertices.push(block_face[index] + (chunk_position.x * CHUNK_SIZE as i32
-                + block_position.x) as f32);
-            index += 1;
-            vertices.push(block_face[index] + (chunk_position.y * CHUNK_SIZE as i32
-                + block_position.y) as f32);
-            index += 1;
-            vertices.push(block_face[index] + (chunk_position.z * CHUNK_SIZE as i32
-                + block_position.z) as f32);
-            index += 1;
-            self.light.push(cardinal_light);
+        let mut centre = glm::vec3(0., 0., 0.);
+        for corner in 0..4 {
+            let x = block_face[corner * 3] as i32 + block_position.x;
+            let y = block_face[corner * 3 + 1] as i32 + block_position.y;
+            let z = block_face[corner * 3 + 2] as i32 + block_position.z;
+            debug_assert!((0..=16).contains(&x) && (0..512).contains(&y) && (0..=16).contains(&z));
+            centre += glm::vec3(x as f32, y as f32, z as f32);
+
+            self.vertices.push(
+                x as GLuint
+                    | (y as GLuint) << Y_SHIFT
+                    | (z as GLuint) << Z_SHIFT
+                    | (corner as GLuint) << CORNER_SHIFT
+                    | light << LIGHT_SHIFT
+                    | texture << TEXTURE_SHIFT
+            );
+            self.vertices.push(tint);
+        }
+
+        if self.is_sorted {
+            self.face_centres.push(self.origin + centre / 4.0);
         }
 
-        indices.push(self.index_index);
-        indices.push(self.index_index + 1);
-        indices.push(self.index_index + 2);
-        indices.push(self.index_index + 2);
-        indices.push(self.index_index + 3);
-        indices.push(self.index_index);
+        self.indices.push(self.index_index);
+        self.indices.push(self.index_index + 1);
+        self.indices.push(self.index_index + 2);
+        self.indices.push(self.index_index + 2);
+        self.indices.push(self.index_index + 3);
+        self.indices.push(self.index_index);
         self.index_index += 4;
     }
 
     pub fn buffer_mesh(&mut self) {
-        self.model.add_data(&self.mesh);
-        self.model.add_vbo(1, &self.light);
+        self.delete_data();
+        if !self.indices.is_empty() {
+            self.range = Some(ChunkBufferArena::allocate(&self.vertices, &self.indices, &self.origin));
+        }
 
-        self.mesh.vertex_positions.clear();
-        self.mesh.texture_coords.clear();
-        self.mesh.indices.clear();
-        self.light.clear();
+        self.vertices = Vec::new();
+        self.indices = Vec::new();
 
-        self.mesh.vertex_positions.shrink_to_fit();
-        self.mesh.texture_coords.shrink_to_fit();
-        self.mesh.indices.shrink_to_fit();
-        self.light.shrink_to_fit();
+        self.index_index = 0;
+
+        if self.is_sorted {
+            self.buffered_face_centres = mem::take(&mut self.face_centres);
+            self.last_sort_position = None;
+        }
+    }
 
+    /// @brief Drops the faces added since the last `buffer_mesh`, keeping the buffered ones.
+    pub fn clear_faces(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.face_centres.clear();
         self.index_index = 0;
     }
 
-    pub fn get_model(&self) -> &Model {
-        &self.model
+    /// @brief Size of the faces added since the last `buffer_mesh`, as they will be uploaded.
+    pub fn get_pending_bytes(&self) -> usize {
+        mem::size_of_val(self.vertices.as_slice()) + mem::size_of_val(self.indices.as_slice())
+    }
+
+    /// @brief Reorders the buffered faces from the furthest to the closest to the camera.
+    /// Nothing happens until the camera moved far enough since the last sort.
+    /// @param camera_position
+    pub fn sort_faces(&mut self, camera_position: &glm::TVec3<f32>) {
+        if self.buffered_face_centres.is_empty() {
+            return;
+        }
+        if let Some(last_position) = self.last_sort_position {
+            if glm::distance2(&last_position, camera_position) < RESORT_DISTANCE * RESORT_DISTANCE {
+                return;
+            }
+        }
+        self.last_sort_position = Some(*camera_position);
+
+        let distances: Vec<f32> = self.buffered_face_centres.iter()
+            .map(|centre| glm::distance2(centre, camera_position))
+            .collect();
+        let mut order: Vec<usize> = (0..distances.len()).collect();
+        order.sort_by(|a, b| {
+            distances[*b].partial_cmp(&distances[*a]).unwrap_or(Ordering::Equal)
+        });
+
+        let indices: Vec<GLuint> = order.iter()
+            .flat_map(|face| {
+                let first = *face as GLuint * 4;
+                [first, first + 1, first + 2, first + 2, first + 3, first]
+            })
+            .collect();
+        ChunkBufferArena::update_indices(self.range.as_ref().unwrap(), &indices);
+    }
+
+    /// @brief Where the mesh lies in the chunk buffer arena, if it has been buffered and has any face.
+    pub fn get_range(&self) -> Option<&ArenaRange> {
+        self.range.as_ref()
     }
 
     pub fn delete_data(&mut self) {
-        self.model.delete_data();
+        if let Some(range) = self.range.take() {
+            ChunkBufferArena::free(&range);
+        }
+    }
+}
+
+impl Drop for ChunkMesh {
+    fn drop(&mut self) {
+        self.delete_data();
     }
 }
 
@@ -110,8 +219,20 @@ impl ChunkMeshCollection {
     pub fn new(
         solid_mesh: ChunkMesh,
         water_mesh: ChunkMesh,
-        flora_mesh: ChunkMesh
+        flora_mesh: ChunkMesh,
+        translucent_mesh: ChunkMesh
     ) -> Self {
-        Self { solid_mesh, water_mesh, flora_mesh }
+        Self { solid_mesh, water_mesh, flora_mesh, translucent_mesh }
+    }
+}
+
+impl Default for ChunkMeshCollection {
+    fn default() -> Self {
+        Self::new(
+            ChunkMesh::default(),
+            ChunkMesh::new_sorted(),
+            ChunkMesh::default(),
+            ChunkMesh::new_sorted()
+        )
     }
 }
\ No newline at end of file
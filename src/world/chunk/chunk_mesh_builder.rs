@@ -16,18 +16,20 @@
 
 use std::sync::Arc;
 use gl::types::GLfloat;
-use sfml::system::{Vector2i, Vector3i};
-use crate::world::block::block_data::{BlockMeshType, BlockShaderType};
-use crate::world::block::block_database::BlockDatabase;
+use sfml::system::Vector3i;
+use crate::world::block::block_data::{BlockDataHolder, BlockMeshType, BlockShaderType, BlockTransparency};
+use crate::world::block::block_database::{BlockDatabase, NO_TINT};
 use crate::world::block::block_id::BlockId;
 use crate::world::block::chunk_block::ChunkBlock;
 use crate::world::chunk::chunk::IChunk;
-use crate::world::chunk::chunk_mesh::ChunkMeshCollection;
+use crate::world::chunk::chunk_mesh::{ChunkMesh, ChunkMeshCollection};
 use crate::world::chunk::chunk_section::ChunkSection;
-use crate::world::world_constants::{CHUNK_SIZE, CHUNK_VOLUME};
+use crate::world::world_constants::{CHUNK_AREA, CHUNK_SIZE, CHUNK_VOLUME};
 
 pub struct ChunkMeshBuilder<'a> {
-    p_chunk: &'a mut ChunkSection
+    p_chunk: &'a mut ChunkSection,
+    /// Blended temperature and humidity of every column, worked out once a tinted block needs it
+    climates: Vec<Option<(f32, f32)>>
 }
 
 #[derive(Copy, Clone, Default)]
@@ -40,27 +42,31 @@ struct AdjacentBlockPositions {
     pub back: Vector3i
 }
 
-const FRONT_FACE: [GLfloat; 12] = [0., 0., 1., 1., 0., 1., 1., 1., 1., 0., 1., 1.];
-const BACK_FACE: [GLfloat; 12] = [1., 0., 0., 0., 0., 0., 0., 1., 0., 1., 1., 0.];
-const LEFT_FACE: [GLfloat; 12] = [0., 0., 0., 0., 0., 1., 0., 1., 1., 0., 1., 0.];
-const RIGHT_FACE: [GLfloat; 12] = [1., 0., 1., 1., 0., 0., 1., 1., 0., 1., 1., 1.];
-const TOP_FACE: [GLfloat; 12] = [0., 1., 1., 1., 1., 1., 1., 1., 0., 0., 1., 0.];
+pub(crate) const FRONT_FACE: [GLfloat; 12] = [0., 0., 1., 1., 0., 1., 1., 1., 1., 0., 1., 1.];
+pub(crate) const BACK_FACE: [GLfloat; 12] = [1., 0., 0., 0., 0., 0., 0., 1., 0., 1., 1., 0.];
+pub(crate) const LEFT_FACE: [GLfloat; 12] = [0., 0., 0., 0., 0., 1., 0., 1., 1., 0., 1., 0.];
+pub(crate) const RIGHT_FACE: [GLfloat; 12] = [1., 0., 1., 1., 0., 0., 1., 1., 0., 1., 1., 1.];
+pub(crate) const TOP_FACE: [GLfloat; 12] = [0., 1., 1., 1., 1., 1., 1., 1., 0., 0., 1., 0.];
 const BOTTOM_FACE: [GLfloat; 12] = [0., 0., 0., 1., 0., 0., 1., 0., 1., 0., 0., 1.];
 
 const X_FACE_1: [GLfloat; 12] = [0., 0., 0., 1., 0., 1., 1., 1., 1., 0., 1., 0.];
 const X_FACE_2: [GLfloat; 12] = [0., 0., 1., 1., 0., 0., 1., 1., 0., 0., 1., 1.];
 
-const LIGHT_TOP: GLfloat = 1.0;
-const LIGHT_X: GLfloat = 0.8;
-const LIGHT_Z: GLfloat = 0.6;
+pub(crate) const LIGHT_TOP: GLfloat = 1.0;
+pub(crate) const LIGHT_X: GLfloat = 0.8;
+pub(crate) const LIGHT_Z: GLfloat = 0.6;
 const LIGHT_BOT: GLfloat = 0.4;
 
+/// Columns on each side whose climate is averaged into a tint, so that colours fade across biome borders.
+const TINT_BLEND_RADIUS: i32 = 2;
+
 impl<'a> ChunkMeshBuilder<'a> {
     pub fn new(
         chunk: &'a mut ChunkSection
     ) -> Self {
         Self {
-            p_chunk: chunk
+            p_chunk: chunk,
+            climates: vec![None; CHUNK_AREA]
         }
     }
 
@@ -86,9 +92,16 @@ impl<'a> ChunkMeshBuilder<'a> {
 
             let p_block_data = block.get_data();
             let data = Arc::clone(&p_block_data);
+            let (top_tint, tint) = Self::block_tints(
+                &mut self.climates,
+                self.p_chunk,
+                data.read().unwrap().block_data(),
+                x as _,
+                z as _
+            );
 
             if data.read().unwrap().block_data().mesh_type == BlockMeshType::X {
-                Self::add_x_block_to_mesh(&mut self.p_chunk.meshes, self.p_chunk.location, block, &data.read().unwrap().block_data().tex_top_coord, &position);
+                Self::add_x_block_to_mesh(&mut self.p_chunk.meshes, self.p_chunk.location, block, &data.read().unwrap().block_data().tex_top, &position, tint);
                 continue;
             }
 
@@ -114,9 +127,10 @@ impl<'a> ChunkMeshBuilder<'a> {
                     &mut self.p_chunk.meshes,
                     block,
                     BOTTOM_FACE,
-                    &data.read().unwrap().block_data().tex_bottom_coord,
+                    &data.read().unwrap().block_data().tex_bottom,
                     &position,
-                    LIGHT_BOT
+                    LIGHT_BOT,
+                    tint
                 );
             }
             let smf = {
@@ -137,9 +151,10 @@ impl<'a> ChunkMeshBuilder<'a> {
                 &mut self.p_chunk.meshes,
                 block,
                 TOP_FACE,
-                &data.read().unwrap().block_data().tex_top_coord,
+                &data.read().unwrap().block_data().tex_top,
                 &position,
-                LIGHT_TOP
+                LIGHT_TOP,
+                top_tint
             );
 
             // Left/ Right
@@ -161,9 +176,10 @@ impl<'a> ChunkMeshBuilder<'a> {
                 &mut self.p_chunk.meshes,
                 block,
                 LEFT_FACE,
-                &data.read().unwrap().block_data().tex_side_coord,
+                &data.read().unwrap().block_data().tex_side,
                 &position,
-                LIGHT_X
+                LIGHT_X,
+                tint
             );
             let smf = {
                 let block = self.p_chunk.get_block(directions.right.x, directions.right.y, directions.right.z);
@@ -183,9 +199,10 @@ impl<'a> ChunkMeshBuilder<'a> {
                 &mut self.p_chunk.meshes,
                 block,
                 RIGHT_FACE,
-                &data.read().unwrap().block_data().tex_side_coord,
+                &data.read().unwrap().block_data().tex_side,
                 &position,
-                LIGHT_X
+                LIGHT_X,
+                tint
             );
 
             // Front/ Back
@@ -207,9 +224,10 @@ impl<'a> ChunkMeshBuilder<'a> {
                 &mut self.p_chunk.meshes,
                 block,
                 FRONT_FACE,
-                &data.read().unwrap().block_data().tex_side_coord,
+                &data.read().unwrap().block_data().tex_side,
                 &position,
-                LIGHT_Z
+                LIGHT_Z,
+                tint
             );
             let smf = {
                 let block = self.p_chunk.get_block(directions.back.x, directions.back.y, directions.back.z);
@@ -229,9 +247,10 @@ impl<'a> ChunkMeshBuilder<'a> {
                 &mut self.p_chunk.meshes,
                 block,
                 BACK_FACE,
-                &data.read().unwrap().block_data().tex_side_coord,
+                &data.read().unwrap().block_data().tex_side,
                 &position,
-                LIGHT_Z
+                LIGHT_Z,
+                tint
             );
         }
     }
@@ -240,31 +259,15 @@ impl<'a> ChunkMeshBuilder<'a> {
         meshes: &mut ChunkMeshCollection,
         location: Vector3i,
         block: &ChunkBlock,
-        texture_coords: &Vector2i,
-        block_position: &Vector3i
+        texture: &str,
+        block_position: &Vector3i,
+        tint: [GLfloat; 3]
     ) {
-        let tex_coords = BlockDatabase::get().texture_atlas.get_texture(texture_coords);
+        let texture = BlockDatabase::get().face_texture(texture);
 
-        match block.get_data().read().unwrap().block_data().shader_type {
-            BlockShaderType::Chunk => {
-                meshes.solid_mesh.add_face(X_FACE_1, tex_coords, &location,
-                                                        block_position, LIGHT_X);
-                meshes.solid_mesh.add_face(X_FACE_2, tex_coords, &location,
-                                                        block_position, LIGHT_X);
-            }
-            BlockShaderType::Liquid => {
-                meshes.water_mesh.add_face(X_FACE_1, tex_coords, &location,
-                                                        block_position, LIGHT_X);
-                meshes.water_mesh.add_face(X_FACE_2, tex_coords, &location,
-                                                        block_position, LIGHT_X);
-            }
-            BlockShaderType::Flora => {
-                meshes.flora_mesh.add_face(X_FACE_1, tex_coords, &location,
-                                                        block_position, LIGHT_X);
-                meshes.flora_mesh.add_face(X_FACE_2, tex_coords, &location,
-                                                        block_position, LIGHT_X);
-            }
-        }
+        let mesh = Self::select_mesh(meshes, block);
+        mesh.add_face(X_FACE_1, texture, &location, block_position, LIGHT_X, tint);
+        mesh.add_face(X_FACE_2, texture, &location, block_position, LIGHT_X, tint);
     }
 
     fn try_add_face_to_mesh(
@@ -273,42 +276,79 @@ impl<'a> ChunkMeshBuilder<'a> {
         meshes: &mut ChunkMeshCollection,
         block: &ChunkBlock,
         block_face: [GLfloat; 12],
-        texture_coords: &Vector2i,
+        texture: &str,
         block_position: &Vector3i,
-        cardinal_light: GLfloat
+        cardinal_light: GLfloat,
+        tint: [GLfloat; 3]
     ) {
         if should_make_face {
-            let tex_coords = BlockDatabase::get().texture_atlas.get_texture(texture_coords);
-
-            match block.get_data().read().unwrap().block_data().shader_type {
-                BlockShaderType::Chunk => {
-                    meshes.solid_mesh.add_face(
-                        block_face,
-                        tex_coords,
-                        &location,
-                        block_position,
-                        cardinal_light
-                    );
-                }
-                BlockShaderType::Liquid => {
-                    meshes.water_mesh.add_face(
-                        block_face,
-                        tex_coords,
-                        &location,
-                        block_position,
-                        cardinal_light
-                    );
-                }
-                BlockShaderType::Flora => {
-                    meshes.flora_mesh.add_face(
-                        block_face,
-                        tex_coords,
-                        &location,
-                        block_position,
-                        cardinal_light
-                    );
+            let texture = BlockDatabase::get().face_texture(texture);
+
+            Self::select_mesh(meshes, block).add_face(
+                block_face,
+                texture,
+                &location,
+                block_position,
+                cardinal_light,
+                tint
+            );
+        }
+    }
+
+    /// @brief Tint of the top face and of the other faces of a block, depending on the climate around it.
+    /// @param climates Blended climates of the section's columns worked out so far.
+    /// @param chunk
+    /// @param data
+    /// @param x Column within the section.
+    /// @param z
+    fn block_tints(
+        climates: &mut [Option<(f32, f32)>],
+        chunk: &ChunkSection,
+        data: &BlockDataHolder,
+        x: i32,
+        z: i32
+    ) -> ([GLfloat; 3], [GLfloat; 3]) {
+        let Some(tint) = data.tint.as_ref() else {
+            return (NO_TINT, NO_TINT);
+        };
+
+        let index = (x * CHUNK_SIZE as i32 + z) as usize;
+        let (temperature, humidity) = *climates[index]
+            .get_or_insert_with(|| Self::blended_climate(chunk, x, z));
+        let colour = BlockDatabase::get().tint(&tint.colour_map, temperature, humidity);
+        (colour, if tint.is_top_only { NO_TINT } else { colour })
+    }
+
+    /// @brief Temperature and humidity of a column averaged with the columns around it, from 0 to 1.
+    fn blended_climate(chunk: &ChunkSection, x: i32, z: i32) -> (f32, f32) {
+        let mut temperature = 0.0;
+        let mut humidity = 0.0;
+        for dx in -TINT_BLEND_RADIUS..=TINT_BLEND_RADIUS {
+            for dz in -TINT_BLEND_RADIUS..=TINT_BLEND_RADIUS {
+                let climate = chunk.get_climate(x + dx, z + dz);
+                temperature += climate.temperature as f32;
+                humidity += climate.humidity as f32;
+            }
+        }
+
+        let samples = ((2 * TINT_BLEND_RADIUS + 1) * (2 * TINT_BLEND_RADIUS + 1)) as f32 * 255.0;
+        (temperature / samples, humidity / samples)
+    }
+
+    /// @brief Mesh the faces of the given block belong to, based on its shader and transparency.
+    fn select_mesh<'m>(meshes: &'m mut ChunkMeshCollection, block: &ChunkBlock) -> &'m mut ChunkMesh {
+        let data = block.get_data();
+        let data = data.read().unwrap();
+        match data.block_data().shader_type {
+            BlockShaderType::Chunk => {
+                if data.block_data().transparency == BlockTransparency::Blended {
+                    &mut meshes.translucent_mesh
+                } else {
+                    &mut meshes.solid_mesh
                 }
             }
+            BlockShaderType::Liquid => &mut meshes.water_mesh,
+            BlockShaderType::Flora => &mut meshes.flora_mesh
         }
     }
 
@@ -25,6 +25,8 @@ use crate::world::block::chunk_block::ChunkBlock;
 use crate::world::chunk::chunk::IChunk;
 use crate::world::chunk::chunk_mesh::ChunkMeshCollection;
 use crate::world::chunk::chunk_mesh_builder::ChunkMeshBuilder;
+use crate::world::chunk::section_visibility::SectionVisibility;
+use crate::world::generation::biome::biome::Climate;
 use crate::world::world::World;
 use crate::world::world_constants::{CHUNK_AREA, CHUNK_SIZE, CHUNK_VOLUME};
 
@@ -38,13 +40,16 @@ pub struct ChunkSection {
     layers: [Layer; CHUNK_SIZE],
 
     pub(crate) meshes: ChunkMeshCollection,
+    visibility: SectionVisibility,
     pub aabb: AABB,
     pub(crate) location: Vector3i,
 
     p_world: Arc<UnsafeCellWrapper<World>>,
 
     has_mesh: bool,
-    has_buffered_mesh: bool
+    has_buffered_mesh: bool,
+    // Meshed again since it was last buffered, the previous mesh is drawn until then
+    is_mesh_pending: bool
 }
 
 impl Layer {
@@ -70,11 +75,13 @@ impl ChunkSection {
             blocks: [ChunkBlock::default(); CHUNK_VOLUME],
             layers: Default::default(),
             meshes: Default::default(),
+            visibility: SectionVisibility::all(),
             aabb: AABB::new(&glm::vec3(CHUNK_SIZE as _, CHUNK_SIZE as _, CHUNK_SIZE as _)),
             location,
             p_world: world,
             has_mesh: false,
-            has_buffered_mesh: false
+            has_buffered_mesh: false,
+            is_mesh_pending: false
         };
         result.aabb.update(&glm::vec3(
             (location.x * CHUNK_SIZE as i32) as _,
@@ -95,18 +102,47 @@ impl ChunkSection {
     pub fn has_buffered(&self) -> bool {
         self.has_buffered_mesh
     }
+
+    /// @brief Whether the section has been meshed since it was last buffered.
+    pub fn is_mesh_pending(&self) -> bool {
+        self.is_mesh_pending
+    }
     
     pub fn make_mesh(&mut self) {
+        // A mesh that never made it to the GPU is outdated already
+        self.meshes.solid_mesh.clear_faces();
+        self.meshes.water_mesh.clear_faces();
+        self.meshes.flora_mesh.clear_faces();
+        self.meshes.translucent_mesh.clear_faces();
+
         ChunkMeshBuilder::new(self).build_mesh();
+        self.visibility = SectionVisibility::compute(&self.blocks);
         self.has_mesh = true;
-        self.has_buffered_mesh = false;
+        self.is_mesh_pending = true;
     }
     
     pub fn buffer_mesh(&mut self) {
         self.meshes.solid_mesh.buffer_mesh();
         self.meshes.water_mesh.buffer_mesh();
         self.meshes.flora_mesh.buffer_mesh();
+        self.meshes.translucent_mesh.buffer_mesh();
         self.has_buffered_mesh = true;
+        self.is_mesh_pending = false;
+    }
+
+    /// @brief Size of the mesh waiting to be buffered.
+    pub fn get_pending_bytes(&self) -> usize {
+        self.meshes.solid_mesh.get_pending_bytes()
+            + self.meshes.water_mesh.get_pending_bytes()
+            + self.meshes.flora_mesh.get_pending_bytes()
+            + self.meshes.translucent_mesh.get_pending_bytes()
+    }
+
+    /// @brief Keeps the blended faces ordered back-to-front as seen from the camera.
+    /// @param camera_position
+    pub fn sort_translucent_faces(&mut self, camera_position: &glm::TVec3<f32>) {
+        self.meshes.water_mesh.sort_faces(camera_position);
+        self.meshes.translucent_mesh.sort_faces(camera_position);
     }
     
     pub fn exec_on_layer<R>(&self, y: i32, func: impl FnOnce(&Layer) -> R) -> R {
@@ -140,6 +176,24 @@ impl ChunkSection {
         }
     }
     
+    /// @brief Climate of a column given relative to the section, which may lie in a neighbouring chunk.
+    pub fn get_climate(&self, x: i32, z: i32) -> Climate {
+        let location = self.to_world_position(x, 0, z);
+        unsafe {
+            (*self.p_world.get()).get_climate(location.x, location.z)
+        }
+    }
+
+    /// @brief Which faces can be seen from which others, worked out when the section was meshed.
+    /// A section without a mesh may be seen through from anywhere.
+    pub fn get_visibility(&self) -> SectionVisibility {
+        if self.has_mesh {
+            self.visibility
+        } else {
+            SectionVisibility::all()
+        }
+    }
+
     pub fn get_meshes(&self) -> &ChunkMeshCollection {
         &self.meshes
     }
@@ -148,9 +202,9 @@ impl ChunkSection {
         if self.has_mesh {
             self.has_buffered_mesh = false;
             self.has_mesh = false;
-            self.meshes.solid_mesh.delete_data();
-            self.meshes.water_mesh.delete_data();
-            self.meshes.flora_mesh.delete_data();
+            self.is_mesh_pending = false;
+            // Dropping the meshes frees their buffers, and any faces not buffered yet
+            self.meshes = ChunkMeshCollection::default();
         }
     }
     
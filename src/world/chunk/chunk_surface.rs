@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sfml::system::Vector2i;
+use crate::world::block::chunk_block::ChunkBlock;
+use crate::world::generation::biome::biome::Climate;
+use crate::world::world_constants::{CHUNK_AREA, CHUNK_SIZE};
+
+/// @brief Top of a single column: the highest solid block and the climate it grows in.
+#[derive(Copy, Clone, Default)]
+pub struct SurfaceColumn {
+    pub height: i32,
+    pub block: ChunkBlock,
+    pub climate: Climate
+}
+
+/// @brief Heights and top blocks of a chunk column, all that is needed to draw it from afar.
+pub struct ChunkSurface {
+    columns: Vec<SurfaceColumn>,
+    location: Vector2i
+}
+
+impl ChunkSurface {
+    pub fn new(location: Vector2i) -> Self {
+        Self {
+            columns: vec![SurfaceColumn::default(); CHUNK_AREA],
+            location
+        }
+    }
+
+    pub fn get_column(&self, x: i32, z: i32) -> &SurfaceColumn {
+        &self.columns[(x * CHUNK_SIZE as i32 + z) as usize]
+    }
+
+    pub fn set_column(&mut self, x: i32, z: i32, column: SurfaceColumn) {
+        self.columns[(x * CHUNK_SIZE as i32 + z) as usize] = column;
+    }
+
+    pub fn get_location(&self) -> Vector2i {
+        self.location
+    }
+
+    pub fn get_min_height(&self) -> i32 {
+        self.columns.iter().map(|column| column.height).min().unwrap()
+    }
+
+    pub fn get_max_height(&self) -> i32 {
+        self.columns.iter().map(|column| column.height).max().unwrap()
+    }
+}
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use sfml::SfBox;
+use sfml::system::Clock;
+use crate::maths::vector2xz::VectorXZ;
+
+/// @brief How much mesh data may be uploaded to the GPU in a single frame.
+/// The most urgent mesh is always uploaded, so that loading never stalls.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct UploadBudget {
+    /// In bytes, 0 for no limit
+    pub max_bytes: usize,
+    /// In milliseconds, 0 for no limit
+    pub max_time: f32
+}
+
+/// @brief Meshes uploaded last frame, and those still waiting.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct UploadStats {
+    pub queued_sections: usize,
+    pub queued_lods: usize,
+    pub uploaded_meshes: usize,
+    pub uploaded_bytes: usize,
+    /// In milliseconds
+    pub upload_time: f32,
+    /// The budget the uploads were held to
+    pub budget: UploadBudget
+}
+
+/// @brief Section that has been meshed but not buffered yet. Sections the camera can see
+/// come first, then the closest ones.
+pub struct PendingSection {
+    pub column: VectorXZ,
+    pub index: i32,
+    is_visible: bool,
+    distance: f32
+}
+
+/// @brief Counts the uploads of one frame against the budget.
+pub struct UploadTracker {
+    budget: UploadBudget,
+    clock: SfBox<Clock>,
+    stats: UploadStats
+}
+
+impl UploadBudget {
+    pub fn unlimited() -> Self {
+        Self {
+            max_bytes: 0,
+            max_time: 0.
+        }
+    }
+}
+
+impl PendingSection {
+    /// @param column
+    /// @param index Section index within the column.
+    /// @param is_visible Whether the section survived frustum and occlusion culling.
+    /// @param distance Squared distance from the camera to the section's centre.
+    pub fn new(column: VectorXZ, index: i32, is_visible: bool, distance: f32) -> Self {
+        Self { column, index, is_visible, distance }
+    }
+}
+
+impl PartialEq for PendingSection {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PendingSection {}
+
+impl PartialOrd for PendingSection {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PendingSection {
+    /// The greatest section is the most urgent one, as `BinaryHeap` pops it first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.is_visible.cmp(&other.is_visible)
+            .then_with(|| other.distance.total_cmp(&self.distance))
+    }
+}
+
+impl UploadTracker {
+    pub fn new(budget: UploadBudget) -> Self {
+        Self {
+            budget,
+            clock: Clock::start(),
+            stats: UploadStats::default()
+        }
+    }
+
+    /// @brief Whether another mesh may be uploaded this frame.
+    pub fn can_upload(&self) -> bool {
+        if self.stats.uploaded_meshes == 0 {
+            return true;
+        }
+        let is_within_bytes = self.budget.max_bytes == 0 || self.stats.uploaded_bytes < self.budget.max_bytes;
+        let is_within_time = self.budget.max_time <= 0. ||
+            self.clock.elapsed_time().as_seconds() * 1000. < self.budget.max_time;
+        is_within_bytes && is_within_time
+    }
+
+    /// @param bytes Size of the vertices and indices uploaded.
+    pub fn add_upload(&mut self, bytes: usize) {
+        self.stats.uploaded_meshes += 1;
+        self.stats.uploaded_bytes += bytes;
+    }
+
+    /// @brief What was uploaded, along with how many meshes are left for the next frames.
+    /// @param queued_sections
+    /// @param queued_lods
+    pub fn finish(mut self, queued_sections: usize, queued_lods: usize) -> UploadStats {
+        self.stats.queued_sections = queued_sections;
+        self.stats.queued_lods = queued_lods;
+        self.stats.upload_time = self.clock.elapsed_time().as_seconds() * 1000.;
+        self.stats.budget = self.budget;
+        self.stats
+    }
+}
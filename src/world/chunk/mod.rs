@@ -18,4 +18,9 @@ pub mod chunk_manager;
 pub mod chunk;
 pub mod chunk_section;
 pub mod chunk_mesh;
-pub mod chunk_mesh_builder;
\ No newline at end of file
+pub mod chunk_mesh_builder;
+pub mod chunk_surface;
+pub mod chunk_lod;
+pub mod section_visibility;
+pub mod chunk_buffer_arena;
+pub mod mesh_upload_queue;
\ No newline at end of file
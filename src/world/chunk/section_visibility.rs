@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use nalgebra_glm::IVec3;
+use crate::world::block::chunk_block::ChunkBlock;
+use crate::world::world_constants::{CHUNK_AREA, CHUNK_SIZE, CHUNK_VOLUME};
+
+/// @brief One of the six faces of a chunk section, named like the adjacent block positions
+/// of the mesh builder.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SectionFace {
+    Down = 0,
+    Up,
+    Left,
+    Right,
+    Back,
+    Front
+}
+
+impl SectionFace {
+    pub const ALL: [SectionFace; 6] = [
+        SectionFace::Down,
+        SectionFace::Up,
+        SectionFace::Left,
+        SectionFace::Right,
+        SectionFace::Back,
+        SectionFace::Front
+    ];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            SectionFace::Down => SectionFace::Up,
+            SectionFace::Up => SectionFace::Down,
+            SectionFace::Left => SectionFace::Right,
+            SectionFace::Right => SectionFace::Left,
+            SectionFace::Back => SectionFace::Front,
+            SectionFace::Front => SectionFace::Back
+        }
+    }
+
+    /// @brief Position of the adjacent section behind this face, relative to the section.
+    pub fn offset(self) -> IVec3 {
+        match self {
+            SectionFace::Down => IVec3::new(0, -1, 0),
+            SectionFace::Up => IVec3::new(0, 1, 0),
+            SectionFace::Left => IVec3::new(-1, 0, 0),
+            SectionFace::Right => IVec3::new(1, 0, 0),
+            SectionFace::Back => IVec3::new(0, 0, -1),
+            SectionFace::Front => IVec3::new(0, 0, 1)
+        }
+    }
+}
+
+/// @brief Which faces of a chunk section can be seen from which other faces, through blocks
+/// that are not opaque.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SectionVisibility {
+    // One bit for every pair of faces
+    connections: u64
+}
+
+impl SectionVisibility {
+    /// @brief Every face sees every other, as for a section made of air only.
+    pub fn all() -> Self {
+        Self { connections: (1 << 36) - 1 }
+    }
+
+    /// @brief Flood fills the blocks of a section that are not opaque, and connects all the
+    /// faces that each filled region touches.
+    /// @param blocks Blocks of the section, indexed like `ChunkSection::blocks`.
+    pub fn compute(blocks: &[ChunkBlock; CHUNK_VOLUME]) -> Self {
+        // Looking up a block's data takes a lock, so it is only done once per block type
+        let mut opaque_types: [Option<bool>; 256] = [None; 256];
+        let is_open: Vec<bool> = blocks.iter()
+            .map(|block| {
+                !*opaque_types[block.id as usize].get_or_insert_with(|| {
+                    block.get_data().read().unwrap().block_data().is_opaque
+                })
+            })
+            .collect();
+
+        if is_open.iter().all(|it| *it) {
+            return Self::all();
+        }
+
+        let mut result = Self { connections: 0 };
+        let mut is_visited = vec![false; CHUNK_VOLUME];
+        let mut stack: Vec<usize> = Vec::new();
+        for start in 0..CHUNK_VOLUME {
+            if !is_open[start] || is_visited[start] {
+                continue;
+            }
+
+            let mut faces: Vec<SectionFace> = Vec::new();
+            is_visited[start] = true;
+            stack.push(start);
+            while let Some(index) = stack.pop() {
+                let position = IVec3::new(
+                    (index % CHUNK_SIZE) as _,
+                    (index / CHUNK_AREA) as _,
+                    ((index / CHUNK_SIZE) % CHUNK_SIZE) as _
+                );
+
+                for face in SectionFace::ALL {
+                    let next = position + face.offset();
+                    if next.iter().any(|it| *it < 0 || *it >= CHUNK_SIZE as i32) {
+                        if !faces.contains(&face) {
+                            faces.push(face);
+                        }
+                        continue;
+                    }
+
+                    let next_index = (next.y * CHUNK_AREA as i32 + next.z * CHUNK_SIZE as i32 + next.x) as usize;
+                    if is_open[next_index] && !is_visited[next_index] {
+                        is_visited[next_index] = true;
+                        stack.push(next_index);
+                    }
+                }
+            }
+
+            for from in faces.iter() {
+                for to in faces.iter() {
+                    result.connections |= Self::bit(*from, *to);
+                }
+            }
+        }
+        result
+    }
+
+    pub fn is_connected(&self, from: SectionFace, to: SectionFace) -> bool {
+        self.connections & Self::bit(from, to) != 0
+    }
+
+    fn bit(from: SectionFace, to: SectionFace) -> u64 {
+        1 << (from as u64 * 6 + to as u64)
+    }
+}
@@ -18,8 +18,12 @@ extern crate nalgebra_glm as glm;
 
 use std::ffi::c_void;
 use sfml::window::mouse::Button;
+use crate::item::material;
 use crate::item::material::{ID, Material};
+use crate::player::hunger;
 use crate::player::player::Player;
+use crate::util::random::RandomSingleton;
+use crate::world::block::block_database::BlockDatabase;
 use crate::world::block::block_id::BlockId;
 use crate::world::block::chunk_block::ChunkBlock;
 use crate::world::event::world_event::IWorldEvent;
@@ -68,9 +72,22 @@ impl PlayerDigEvent {
         match self.button_press {
             Button::Left => {
                 let block = world.get_block(x, y, z);
-                let material = Material::from_block_id(BlockId::try_from(
-                    block.id as i32).unwrap());
-                self.p_player.as_mut().add_item(material);
+                let block_id = BlockId::try_from(block.id as i32).unwrap();
+                let material = Material::from_block_id(block_id);
+                let player = self.p_player.as_mut();
+                player.add_item(material);
+                player.add_exhaustion(hunger::EXHAUSTION_BLOCK_BREAK);
+
+                // Some plants occasionally drop something to eat.
+                match block_id {
+                    BlockId::OakLeaf if RandomSingleton::get().int_in_range(0..8) == 0 => {
+                        player.add_item(&material::APPLE);
+                    }
+                    BlockId::TallGrass if RandomSingleton::get().int_in_range(0..20) == 0 => {
+                        player.add_item(&material::BREAD);
+                    }
+                    _ => {}
+                }
                 world.update_chunk(x, y, z);
                 world.set_block(x, y, z, ChunkBlock::new_with_block_type(0));
             }
@@ -78,13 +95,26 @@ impl PlayerDigEvent {
                 let stack = self.p_player.as_mut().get_held_items_mut();
                 let material = stack.material();
 
-                if material.id == ID::Nothing {
+                if material.id == ID::Nothing || !material.is_block {
                     return;
-                } else {
-                    stack.remove();
-                    world.update_chunk(x, y, z);
-                    world.set_block(x, y, z, ChunkBlock::new_with_block_id(material.to_block_id()));
                 }
+
+                // Refuse to place a solid block inside the player.
+                let is_inside_player = {
+                    let block_data = BlockDatabase::get().get_data(material.to_block_id());
+                    let block_data = block_data.read().unwrap();
+                    let block_box = block_data.block_data().collision_box
+                        .offset(&glm::vec3(x as f32, y as f32, z as f32));
+                    block_data.block_data().is_collidable &&
+                        block_box.intersects(&self.p_player.as_ref().get_collision_box())
+                };
+                if is_inside_player {
+                    return;
+                }
+
+                stack.remove();
+                world.update_chunk(x, y, z);
+                world.set_block(x, y, z, ChunkBlock::new_with_block_id(material.to_block_id()));
             }
             _ => {}
         }
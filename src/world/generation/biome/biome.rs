@@ -19,6 +19,30 @@ use crate::util::random::Random;
 use crate::world::block::block_id::BlockId;
 use crate::world::block::chunk_block::ChunkBlock;
 use crate::world::chunk::chunk::Chunk;
+use crate::world::world_constants::WATER_LEVEL;
+
+/// @brief Temperature and humidity of a column, which pick the colours of tinted blocks
+/// from the colour maps of the texture pack.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Climate {
+    /// From 0, the coldest, to 255, the hottest.
+    pub temperature: u8,
+    /// From 0, the driest, to 255, the wettest.
+    pub humidity: u8
+}
+
+impl Climate {
+    pub const fn new(temperature: u8, humidity: u8) -> Self {
+        Self { temperature, humidity }
+    }
+}
+
+impl Default for Climate {
+    /// Mild climate under which the colour maps leave textures as they are.
+    fn default() -> Self {
+        Self::new(150, 150)
+    }
+}
 
 pub trait Biome {
     fn get_plant(&self, rand: &Random) -> ChunkBlock;
@@ -27,11 +51,24 @@ pub trait Biome {
     fn get_beach_block(&self, _rand: &Random) -> ChunkBlock {
         ChunkBlock::new_with_block_id(BlockId::Sand)
     }
+    /// @brief Block on top of the ground at a height, under water, on the beach or above it.
+    /// @param rand
+    /// @param y
+    fn get_surface_block(&self, rand: &Random, y: i32) -> ChunkBlock {
+        if y < WATER_LEVEL as i32 {
+            self.get_under_water_block(rand)
+        } else if y < (WATER_LEVEL + 4) as i32 {
+            self.get_beach_block(rand)
+        } else {
+            self.get_top_block(rand)
+        }
+    }
     fn make_tree(&self, rand: &Random, chunk: &mut Chunk, x: i32, y: i32, z: i32);
 
     fn get_height(&self, x: i32, z: i32, chunk_x: i32, chunk_z: i32) -> i32;
     fn get_tree_frequency(&self) -> i32;
     fn get_plant_frequency(&self) -> i32;
+    fn get_climate(&self) -> Climate;
 }
 
 pub struct BiomeBase {
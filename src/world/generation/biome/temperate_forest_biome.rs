@@ -19,7 +19,7 @@ use crate::util::random::Random;
 use crate::world::block::block_id::BlockId;
 use crate::world::block::chunk_block::ChunkBlock;
 use crate::world::chunk::chunk::Chunk;
-use crate::world::generation::biome::biome::{Biome, BiomeBase};
+use crate::world::generation::biome::biome::{Biome, BiomeBase, Climate};
 use crate::world::generation::structure::tree_generator;
 
 pub struct TemperateForestBiome {
@@ -81,4 +81,8 @@ impl Biome for TemperateForestBiome {
     fn get_plant_frequency(&self) -> i32 {
         self.biome.plant_freq
     }
+
+    fn get_climate(&self) -> Climate {
+        Climate::new(110, 210)
+    }
 }
\ No newline at end of file
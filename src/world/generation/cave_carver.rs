@@ -0,0 +1,237 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use std::f32::consts::PI;
+use std::ops::RangeInclusive;
+use sfml::system::Vector2i;
+use crate::maths::noise_generator::NoiseGenerator;
+use crate::util::array2d::Array2D;
+use crate::util::random::{chunk_seed, Random};
+use crate::world::block::block_id::BlockId;
+use crate::world::block::chunk_block::ChunkBlock;
+use crate::world::chunk::chunk::{Chunk, IChunk};
+use crate::world::world_constants::{CHUNK_SIZE, WATER_LEVEL};
+
+/// Chunks around a chunk whose tunnels can reach into it.
+const CARVER_RANGE: i32 = 8;
+
+/// Layers at the bottom of the world that are never carved.
+const CAVE_FLOOR: i32 = 4;
+/// Columns lower than this lie under water or on the shore, and keep `WATER_ROOF` blocks
+/// above any cave, so that oceans and lakes never drain into one. Caves can open up
+/// anywhere on dry land.
+const DRY_LAND_HEIGHT: i32 = WATER_LEVEL as i32 + 4;
+const WATER_ROOF: i32 = 5;
+
+/// Rock left between the surface and the large caverns.
+const CHEESE_ROOF: i32 = 12;
+/// Noise above this is hollowed out, about 2% of the rock.
+const CHEESE_THRESHOLD: f64 = 0.55;
+
+/// One chunk in this many starts tunnels, and one in this many a ravine.
+const TUNNEL_CHANCE: i32 = 7;
+const RAVINE_CHANCE: i32 = 60;
+const MAX_TUNNELS: i32 = 3;
+
+/// Keeps the tunnels of a chunk apart from its ores, which draw from the same world seed.
+const TUNNEL_SEED_SALT: i64 = 1;
+
+/// @brief How a kind of tunnel winds through the rock.
+struct TunnelShape {
+    /// In blocks, one step each
+    length: RangeInclusive<i32>,
+    start_height: RangeInclusive<i32>,
+    radius: RangeInclusive<f32>,
+    /// Height of the cross-section over its width
+    height_ratio: f32,
+    /// Largest change of direction in a step, and largest slope, in radians
+    max_turn: f32,
+    max_pitch: f32
+}
+
+/// Winding tunnels a few blocks wide.
+const WORM: TunnelShape = TunnelShape {
+    length: 60..=110,
+    start_height: 8..=WATER_LEVEL as i32 + 16,
+    radius: 1.5..=3.0,
+    height_ratio: 1.0,
+    max_turn: 0.25,
+    max_pitch: 0.6
+};
+
+/// Narrow and deep gorges running nearly straight and level.
+const RAVINE: TunnelShape = TunnelShape {
+    length: 70..=110,
+    start_height: 24..=WATER_LEVEL as i32 - 8,
+    radius: 1.5..=2.5,
+    height_ratio: 4.0,
+    max_turn: 0.06,
+    max_pitch: 0.1
+};
+
+/// @brief Hollows out caves, tunnels and ravines in freshly generated terrain.
+///
+/// Large caverns follow 3D noise, and tunnels wind along paths that start in chunks up to
+/// `CARVER_RANGE` away. Every chunk walks the paths of all the tunnels that can reach it
+/// and only carves its own part of them, so tunnels carry on across chunk borders, and
+/// the same seed always carves the same caves.
+pub struct CaveCarver {
+    seed: i32,
+    noise: NoiseGenerator
+}
+
+impl CaveCarver {
+    pub fn new(seed: i32) -> Self {
+        Self {
+            seed,
+            noise: NoiseGenerator::new(seed.wrapping_mul(3))
+        }
+    }
+
+    /// @param chunk Filled with its terrain, before any tree or plant is added.
+    /// @param heights Surface height of every column of the chunk.
+    pub fn carve(&self, chunk: &mut Chunk, heights: &Array2D<i32>) {
+        let location = chunk.get_location();
+        self.carve_cheese(chunk, heights, location);
+
+        for origin_x in location.x - CARVER_RANGE ..= location.x + CARVER_RANGE {
+            for origin_z in location.y - CARVER_RANGE ..= location.y + CARVER_RANGE {
+                // Everything is drawn in the same order whichever chunk is being carved
+                let random = Random::new_with_seed(chunk_seed(self.seed, origin_x, origin_z, TUNNEL_SEED_SALT));
+                if random.int_in_range(0..TUNNEL_CHANCE) == 0 {
+                    for _ in 0..random.int_in_range(1..=MAX_TUNNELS) {
+                        self.carve_tunnel(chunk, heights, &random, origin_x, origin_z, &WORM);
+                    }
+                }
+                if random.int_in_range(0..RAVINE_CHANCE) == 0 {
+                    self.carve_tunnel(chunk, heights, &random, origin_x, origin_z, &RAVINE);
+                }
+            }
+        }
+    }
+
+    fn carve_cheese(&self, chunk: &mut Chunk, heights: &Array2D<i32>, location: Vector2i) {
+        for x in 0..CHUNK_SIZE as i32 {
+            for z in 0..CHUNK_SIZE as i32 {
+                let world_x = (location.x * CHUNK_SIZE as i32 + x) as f64;
+                let world_z = (location.y * CHUNK_SIZE as i32 + z) as f64;
+                let height = *heights.get(x as _, z as _);
+
+                for y in CAVE_FLOOR..height - CHEESE_ROOF {
+                    // Squashed vertically, so that caverns spread out more than up
+                    let value = self.noise.get_noise_3d(world_x / 40.0, y as f64 / 20.0, world_z / 40.0) * 0.65
+                        + self.noise.get_noise_3d(world_x / 16.0, y as f64 / 10.0, world_z / 16.0) * 0.35;
+                    if value > CHEESE_THRESHOLD {
+                        Self::carve_block(chunk, heights, x, y, z);
+                    }
+                }
+            }
+        }
+    }
+
+    /// @brief Walks a tunnel starting in the origin chunk, carving the part inside the chunk.
+    /// Its turns follow noise, so that it winds smoothly rather than jittering.
+    fn carve_tunnel(
+        &self,
+        chunk: &mut Chunk,
+        heights: &Array2D<i32>,
+        random: &Random,
+        origin_x: i32,
+        origin_z: i32,
+        shape: &TunnelShape
+    ) {
+        let mut position = glm::vec3(
+            (origin_x * CHUNK_SIZE as i32) as f32 + random.int_in_range(0.0..CHUNK_SIZE as f32),
+            random.int_in_range(shape.start_height.clone()) as f32,
+            (origin_z * CHUNK_SIZE as i32) as f32 + random.int_in_range(0.0..CHUNK_SIZE as f32)
+        );
+        let length = random.int_in_range(shape.length.clone());
+        let radius = random.int_in_range(shape.radius.clone());
+        let mut yaw = random.int_in_range(0.0..2.0 * PI);
+        let noise_offset = random.int_in_range(0.0..10000.0f64);
+
+        let chunk_min = Self::chunk_min(chunk);
+        for step in 0..length {
+            let t = step as f64;
+            yaw += self.noise.get_noise_3d(t / 12.0, noise_offset, 0.0) as f32 * shape.max_turn;
+            let pitch = self.noise.get_noise_3d(t / 20.0, noise_offset, 50.0) as f32 * shape.max_pitch;
+            position += glm::vec3(yaw.cos() * pitch.cos(), pitch.sin(), yaw.sin() * pitch.cos());
+
+            // Widest halfway along, narrowing towards both ends
+            let width = radius * (0.6 + 0.6 * (PI * step as f32 / length as f32).sin());
+            let is_near = position.x + width >= chunk_min.x as f32
+                && position.x - width < (chunk_min.x + CHUNK_SIZE as i32) as f32
+                && position.z + width >= chunk_min.y as f32
+                && position.z - width < (chunk_min.y + CHUNK_SIZE as i32) as f32;
+            if is_near {
+                Self::carve_ellipsoid(chunk, heights, &position, width, width * shape.height_ratio);
+            }
+        }
+    }
+
+    fn carve_ellipsoid(
+        chunk: &mut Chunk,
+        heights: &Array2D<i32>,
+        centre: &glm::TVec3<f32>,
+        radius: f32,
+        half_height: f32
+    ) {
+        let chunk_min = Self::chunk_min(chunk);
+        let local_x = centre.x - chunk_min.x as f32;
+        let local_z = centre.z - chunk_min.y as f32;
+
+        let min_x = ((local_x - radius).floor() as i32).max(0);
+        let max_x = ((local_x + radius).ceil() as i32).min(CHUNK_SIZE as i32 - 1);
+        let min_z = ((local_z - radius).floor() as i32).max(0);
+        let max_z = ((local_z + radius).ceil() as i32).min(CHUNK_SIZE as i32 - 1);
+        let min_y = (centre.y - half_height).floor() as i32;
+        let max_y = (centre.y + half_height).ceil() as i32;
+
+        for x in min_x..=max_x {
+            for z in min_z..=max_z {
+                for y in min_y..=max_y {
+                    // Distances from the centre of each block
+                    let dx = (x as f32 + 0.5 - local_x) / radius;
+                    let dy = (y as f32 + 0.5 - centre.y) / half_height;
+                    let dz = (z as f32 + 0.5 - local_z) / radius;
+                    if dx * dx + dy * dy + dz * dz < 1.0 {
+                        Self::carve_block(chunk, heights, x, y, z);
+                    }
+                }
+            }
+        }
+    }
+
+    fn carve_block(chunk: &mut Chunk, heights: &Array2D<i32>, x: i32, y: i32, z: i32) {
+        let height = *heights.get(x as _, z as _);
+        if y < CAVE_FLOOR || y > height || (height < DRY_LAND_HEIGHT && y > height - WATER_ROOF) {
+            return;
+        }
+
+        let block = chunk.get_block(x, y, z).id;
+        if block != BlockId::Air as _ && block != BlockId::Water as _ {
+            chunk.set_block(x, y, z, ChunkBlock::new_with_block_id(BlockId::Air));
+        }
+    }
+
+    /// @brief World position of the chunk's first column.
+    fn chunk_min(chunk: &Chunk) -> Vector2i {
+        let location = chunk.get_location();
+        Vector2i::new(location.x * CHUNK_SIZE as i32, location.y * CHUNK_SIZE as i32)
+    }
+}
@@ -16,7 +16,7 @@
 
 use std::sync::Mutex;
 use lazy_static::lazy_static;
-use sfml::system::Vector3i;
+use sfml::system::Vector2i;
 use crate::maths::general_maths::smooth_interpolation;
 use crate::maths::noise_generator::{NoiseGenerator, NoiseParameters};
 use crate::util::array2d::Array2D;
@@ -24,12 +24,16 @@ use crate::util::random::{Random, RandomSingleton};
 use crate::world::block::block_id::BlockId;
 use crate::world::block::chunk_block::ChunkBlock;
 use crate::world::chunk::chunk::{Chunk, IChunk};
+use crate::world::chunk::chunk_surface::{ChunkSurface, SurfaceColumn};
 use crate::world::generation::biome::biome::Biome;
 use crate::world::generation::biome::desert_biome::DesertBiome;
 use crate::world::generation::biome::grassland_biome::GrasslandBiome;
 use crate::world::generation::biome::light_forest::LightForest;
 use crate::world::generation::biome::ocean_biome::OceanBiome;
 use crate::world::generation::biome::temperate_forest_biome::TemperateForestBiome;
+use crate::world::generation::cave_carver::CaveCarver;
+use crate::world::generation::decorations::Decorations;
+use crate::world::generation::ore_generator::{OreGenerator, ORE_FILE};
 use crate::world::generation::terrain_generator::TerrainGenerator;
 use crate::world::world_constants::{CHUNK_SIZE, WATER_LEVEL};
 
@@ -39,6 +43,8 @@ pub struct ClassicOverWorldGenerator {
     biome_map: Array2D<i32>,
 
     random: Random,
+    cave_carver: CaveCarver,
+    ore_generator: OreGenerator,
 
     grass_biome: GrasslandBiome,
     temperate_forest: TemperateForestBiome,
@@ -83,8 +89,7 @@ impl ClassicOverWorldGenerator {
     }
 
     fn set_blocks(&mut self, p_chunk: &mut Chunk, max_height: i32) {
-        let mut trees: Vec<Vector3i> = Vec::new();
-        let mut plants: Vec<Vector3i> = Vec::new();
+        let mut decorations = Decorations::default();
 
         for y in 0..max_height + 1 {
             for x in 0..CHUNK_SIZE {
@@ -106,12 +111,7 @@ impl ClassicOverWorldGenerator {
                                 continue;
                             }
 
-                            if self.random.int_in_range(0..=biome.get_tree_frequency()) == 5 {
-                                trees.push(Vector3i::new(x as _, y + 1, z as _));
-                            }
-                            if self.random.int_in_range(0..=biome.get_tree_frequency()) == 5 {
-                                plants.push(Vector3i::new(x as _, y + 1, z as _));
-                            }
+                            decorations.add_candidates(biome, &self.random, x as _, y, z as _);
                             p_chunk.set_block(x as _, y, z as _,
                                               self.get_biome(x, z).get_top_block(&self.random));
                         } else {
@@ -129,25 +129,23 @@ impl ClassicOverWorldGenerator {
             }
         }
 
-        for plant in plants.iter() {
-            let x = plant.x;
-            let z = plant.z;
-
-            let block = self.get_biome(x as _, z as _).get_plant(&self.random);
-            p_chunk.set_block(x, plant.y, z, block);
-        }
+        self.ore_generator.place_ores(p_chunk);
+        self.cave_carver.carve(p_chunk, &self.height_map);
 
-        for tree in trees.iter() {
-            let x = tree.x;
-            let z = tree.z;
+        decorations.place(p_chunk, &self.random, |x, z| self.get_biome(x as _, z as _));
+    }
 
-            self.get_biome(x as _, z as _).make_tree(&self.random, p_chunk, x, tree.y, z);
+    fn set_climate_map(&self, p_chunk: &mut Chunk) {
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                p_chunk.set_climate(x as _, z as _, self.get_biome(x, z).get_climate());
+            }
         }
     }
 
     fn get_height_in(
         &mut self,
-        p_chunk: &mut Chunk,
+        location: Vector2i,
         x_min: i32,
         z_min: i32,
         x_max: i32,
@@ -156,7 +154,7 @@ impl ClassicOverWorldGenerator {
         let get_height_at = |x: i32, z: i32| {
             let biome = self.get_biome(x as _, z as _);
 
-            biome.get_height(x, z, p_chunk.get_location().x, p_chunk.get_location().y)
+            biome.get_height(x, z, location.x, location.y)
         };
 
         let bottom_left = get_height_at(x_min, z_min) as f32;
@@ -185,19 +183,17 @@ impl ClassicOverWorldGenerator {
         }
     }
 
-    fn get_height_map(&mut self, p_chunk: &mut Chunk) {
+    fn get_height_map(&mut self, location: Vector2i) {
         const HALF_CHUNK: i32 = CHUNK_SIZE as i32 / 2;
         const CHUNK: i32 = CHUNK_SIZE as i32;
 
-        self.get_height_in(p_chunk, 0, 0, HALF_CHUNK, HALF_CHUNK);
-        self.get_height_in(p_chunk, HALF_CHUNK, 0, CHUNK, HALF_CHUNK);
-        self.get_height_in(p_chunk, 0, HALF_CHUNK, HALF_CHUNK, CHUNK);
-        self.get_height_in(p_chunk, HALF_CHUNK, HALF_CHUNK, CHUNK, CHUNK);
+        self.get_height_in(location, 0, 0, HALF_CHUNK, HALF_CHUNK);
+        self.get_height_in(location, HALF_CHUNK, 0, CHUNK, HALF_CHUNK);
+        self.get_height_in(location, 0, HALF_CHUNK, HALF_CHUNK, CHUNK);
+        self.get_height_in(location, HALF_CHUNK, HALF_CHUNK, CHUNK, CHUNK);
     }
 
-    fn get_biome_map(&mut self, p_chunk: &mut Chunk) {
-        let location = p_chunk.get_location();
-
+    fn get_biome_map(&mut self, location: Vector2i) {
         for x in 0..=CHUNK_SIZE {
             for z in 0..=CHUNK_SIZE {
                 let h = BIOME_NOISE_GEN.lock().unwrap().get_height(
@@ -234,6 +230,8 @@ impl Default for ClassicOverWorldGenerator {
             height_map: Array2D::new(CHUNK_SIZE),
             biome_map: Array2D::new(CHUNK_SIZE + 1),
             random: Default::default(),
+            cave_carver: CaveCarver::new(SEED.clone()),
+            ore_generator: OreGenerator::load(ORE_FILE, SEED.clone()),
             grass_biome: GrasslandBiome::new(SEED.clone()),
             temperate_forest: TemperateForestBiome::new(SEED.clone()),
             desert_biome: DesertBiome::new(SEED.clone()),
@@ -249,8 +247,9 @@ impl TerrainGenerator for ClassicOverWorldGenerator {
         let location = chunk.get_location();
         self.random.set_seed(((location.x ^ location.y) << 2) as _);
 
-        self.get_biome_map(chunk);
-        self.get_height_map(chunk);
+        self.get_biome_map(location);
+        self.get_height_map(location);
+        self.set_climate_map(chunk);
 
         let mut max_height = *self.height_map.get_max_value();
 
@@ -258,6 +257,28 @@ impl TerrainGenerator for ClassicOverWorldGenerator {
         self.set_blocks(chunk, max_height);
     }
 
+    fn generate_surface_for(&mut self, location: Vector2i) -> ChunkSurface {
+        self.random.set_seed(((location.x ^ location.y) << 2) as _);
+
+        self.get_biome_map(location);
+        self.get_height_map(location);
+
+        // Same top blocks as `set_blocks`, trees and plants are left out
+        let mut surface = ChunkSurface::new(location);
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let height = *self.height_map.get(x, z);
+                let biome = self.get_biome(x, z);
+                surface.set_column(x as _, z as _, SurfaceColumn {
+                    height,
+                    block: biome.get_surface_block(&self.random, height),
+                    climate: biome.get_climate()
+                });
+            }
+        }
+        surface
+    }
+
     fn get_minimum_spawn_height(&self) -> i32 {
         WATER_LEVEL as _
     }
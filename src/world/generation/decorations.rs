@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use sfml::system::Vector3i;
+use crate::util::random::Random;
+use crate::world::block::block_id::BlockId;
+use crate::world::chunk::chunk::{Chunk, IChunk};
+use crate::world::generation::biome::biome::Biome;
+use crate::world::world_constants::WATER_LEVEL;
+
+/// @brief Plants and trees picked while a generator lays out the ground of a chunk, placed
+/// once the caves are carved.
+#[derive(Default)]
+pub struct Decorations {
+    trees: Vec<Vector3i>,
+    plants: Vec<Vector3i>
+}
+
+impl Decorations {
+    /// @brief Rolls for a tree and a plant on a surface block above the beaches.
+    /// @param biome
+    /// @param rand
+    /// @param x Position of the surface block
+    /// @param y
+    /// @param z
+    pub fn add_candidates(&mut self, biome: &dyn Biome, rand: &Random, x: i32, y: i32, z: i32) {
+        if y < (WATER_LEVEL + 4) as i32 {
+            return;
+        }
+
+        if rand.int_in_range(0..=biome.get_tree_frequency()) == 5 {
+            self.trees.push(Vector3i::new(x, y + 1, z));
+        }
+        if rand.int_in_range(0..=biome.get_plant_frequency()) == 5 {
+            self.plants.push(Vector3i::new(x, y + 1, z));
+        }
+    }
+
+    /// @brief Places the plants, then the trees, that still stand on the ground.
+    /// @param chunk Carved already.
+    /// @param rand
+    /// @param get_biome Biome of a column of the chunk.
+    pub fn place<'a>(
+        mut self,
+        chunk: &mut Chunk,
+        rand: &Random,
+        get_biome: impl Fn(i32, i32) -> &'a dyn Biome
+    ) {
+        // Nothing grows over a cave that broke through the surface
+        let has_ground = |chunk: &Chunk, position: &Vector3i| {
+            chunk.get_block(position.x, position.y - 1, position.z).id != BlockId::Air as _
+        };
+        self.plants.retain(|plant| has_ground(chunk, plant));
+        self.trees.retain(|tree| has_ground(chunk, tree));
+
+        for plant in self.plants.iter() {
+            let block = get_biome(plant.x, plant.z).get_plant(rand);
+            chunk.set_block(plant.x, plant.y, plant.z, block);
+        }
+
+        for tree in self.trees.iter() {
+            get_biome(tree.x, tree.z).make_tree(rand, chunk, tree.x, tree.y, tree.z);
+        }
+    }
+}
@@ -0,0 +1,442 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use lazy_static::lazy_static;
+use sfml::system::Vector2i;
+use crate::maths::noise_generator::NoiseGenerator;
+use crate::util::array2d::Array2D;
+use crate::util::random::{Random, RandomSingleton};
+use crate::world::block::block_id::BlockId;
+use crate::world::block::chunk_block::ChunkBlock;
+use crate::world::chunk::chunk::{Chunk, IChunk};
+use crate::world::chunk::chunk_surface::{ChunkSurface, SurfaceColumn};
+use crate::world::generation::biome::biome::Biome;
+use crate::world::generation::biome::desert_biome::DesertBiome;
+use crate::world::generation::biome::grassland_biome::GrasslandBiome;
+use crate::world::generation::biome::light_forest::LightForest;
+use crate::world::generation::biome::ocean_biome::OceanBiome;
+use crate::world::generation::biome::temperate_forest_biome::TemperateForestBiome;
+use crate::world::generation::cave_carver::CaveCarver;
+use crate::world::generation::decorations::Decorations;
+use crate::world::generation::ore_generator::{OreGenerator, ORE_FILE};
+use crate::world::generation::terrain_generator::TerrainGenerator;
+use crate::world::world_constants::{CHUNK_SIZE, WATER_LEVEL};
+
+/// Density is only worked out at the corners of cells this many blocks wide and high,
+/// and interpolated in between.
+const CELL_WIDTH: usize = 4;
+const CELL_HEIGHT: usize = 8;
+const GRID_WIDTH: usize = CHUNK_SIZE / CELL_WIDTH + 1;
+/// Nothing is solid from this layer up.
+const TERRAIN_TOP: usize = 192;
+const GRID_HEIGHT: usize = TERRAIN_TOP / CELL_HEIGHT + 1;
+/// Layers at the bottom of the world that are always solid.
+const TERRAIN_FLOOR: usize = 3;
+
+/// Columns less continental than this lie in the ocean.
+const OCEAN_EDGE: f64 = -0.15;
+/// Cells around a grid column whose biomes are blended into its shape, so that the ground
+/// slopes from one biome to the next instead of stepping.
+const BLEND_RADIUS: i32 = 2;
+/// The 3D noise mostly lies within -0.4 and 0.4, and is scaled up so that it lifts or sinks
+/// the ground by about its roughness.
+const DENSITY_NOISE_SCALE: f64 = 2.5;
+/// Blocks of dirt under the top block.
+const DIRT_DEPTH: i32 = 3;
+
+/// @brief Piecewise linear curve through points sorted by their input, flat past both ends.
+struct Spline(&'static [(f64, f64)]);
+
+impl Spline {
+    fn get(&self, input: f64) -> f64 {
+        let points = self.0;
+        let first = points[0];
+        let last = points[points.len() - 1];
+        if input <= first.0 {
+            return first.1;
+        }
+        if input >= last.0 {
+            return last.1;
+        }
+
+        let next = points.iter().position(|point| point.0 > input).unwrap();
+        let (x0, y0) = points[next - 1];
+        let (x1, y1) = points[next];
+        y0 + (y1 - y0) * (input - x0) / (x1 - x0)
+    }
+}
+
+/// @brief How the ground of a biome rises further inland and how rough it is where
+/// erosion is low.
+struct TerrainShape {
+    /// Continentalness to the height the ground lies around
+    height: Spline,
+    /// Erosion to how many blocks the 3D noise can lift or sink the ground. Where it is large,
+    /// the noise also leaves overhangs, arches and floating islands.
+    roughness: Spline
+}
+
+const OCEAN_SHAPE: TerrainShape = TerrainShape {
+    height: Spline(&[(-0.6, 34.0), (-0.35, 44.0), (OCEAN_EDGE, 58.0)]),
+    roughness: Spline(&[(-0.6, 8.0), (0.6, 4.0)])
+};
+
+const GRASSLAND_SHAPE: TerrainShape = TerrainShape {
+    height: Spline(&[(OCEAN_EDGE, 62.0), (0.0, 66.0), (0.25, 72.0), (0.5, 84.0)]),
+    roughness: Spline(&[(-0.6, 12.0), (0.0, 6.0), (0.6, 3.0)])
+};
+
+const LIGHT_FOREST_SHAPE: TerrainShape = TerrainShape {
+    height: Spline(&[(OCEAN_EDGE, 62.0), (0.0, 68.0), (0.25, 80.0), (0.5, 96.0)]),
+    roughness: Spline(&[(-0.6, 20.0), (0.0, 10.0), (0.6, 5.0)])
+};
+
+/// Hills turning into cliffs and mountains inland.
+const TEMPERATE_FOREST_SHAPE: TerrainShape = TerrainShape {
+    height: Spline(&[(OCEAN_EDGE, 62.0), (0.0, 72.0), (0.2, 92.0), (0.45, 125.0)]),
+    roughness: Spline(&[(-0.6, 36.0), (-0.2, 20.0), (0.2, 10.0), (0.6, 6.0)])
+};
+
+const DESERT_SHAPE: TerrainShape = TerrainShape {
+    height: Spline(&[(OCEAN_EDGE, 63.0), (0.0, 66.0), (0.5, 78.0)]),
+    roughness: Spline(&[(-0.6, 8.0), (0.6, 3.0)])
+};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+enum BiomeKind {
+    Ocean,
+    #[default]
+    Grassland,
+    LightForest,
+    TemperateForest,
+    Desert
+}
+
+impl BiomeKind {
+    fn get_shape(&self) -> &'static TerrainShape {
+        match self {
+            Self::Ocean => &OCEAN_SHAPE,
+            Self::Grassland => &GRASSLAND_SHAPE,
+            Self::LightForest => &LIGHT_FOREST_SHAPE,
+            Self::TemperateForest => &TEMPERATE_FOREST_SHAPE,
+            Self::Desert => &DESERT_SHAPE
+        }
+    }
+}
+
+/// @brief Large-scale noise of a column, from about -0.6 to 0.6.
+struct ColumnNoise {
+    continentalness: f64,
+    erosion: f64,
+    temperature: f64,
+    humidity: f64
+}
+
+/// @brief Generates chunks from a 3D density field: a block is solid wherever the density
+/// is positive. The field leans from solid below the height picked by the biome to air above
+/// it, and 3D noise bends it into overhangs, arches and floating islands.
+pub struct DensityOverWorldGenerator {
+    /// Density at the corners of the cells of a chunk
+    density_grid: Vec<f64>,
+    /// Highest solid block of every column
+    height_map: Array2D<i32>,
+    biome_map: Array2D<BiomeKind>,
+
+    continentalness_noise: NoiseGenerator,
+    erosion_noise: NoiseGenerator,
+    temperature_noise: NoiseGenerator,
+    humidity_noise: NoiseGenerator,
+    density_noise: NoiseGenerator,
+
+    random: Random,
+    cave_carver: CaveCarver,
+    ore_generator: OreGenerator,
+
+    grass_biome: GrasslandBiome,
+    temperate_forest: TemperateForestBiome,
+    desert_biome: DesertBiome,
+    ocean_biome: OceanBiome,
+    light_forest: LightForest
+}
+
+lazy_static! {
+    static ref SEED: i32 = RandomSingleton::get().int_in_range(424..=325322);
+}
+
+impl DensityOverWorldGenerator {
+    pub fn new() -> Self {
+        log::info!("Seed: {}", SEED.clone());
+        Self::default()
+    }
+
+    /// @brief Sum of octaves of noise, each twice as fine and half as strong as the one before,
+    /// scaled back to the range of a single octave.
+    fn get_octave_noise(noise: &NoiseGenerator, x: f64, y: f64, z: f64, octaves: i32) -> f64 {
+        let mut total = 0.0;
+        let mut total_amplitude = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        for _ in 0..octaves {
+            total += noise.get_noise_3d(x * frequency, y * frequency, z * frequency) * amplitude;
+            total_amplitude += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        total / total_amplitude
+    }
+
+    fn get_column_noise(&self, x: f64, z: f64) -> ColumnNoise {
+        ColumnNoise {
+            continentalness: Self::get_octave_noise(&self.continentalness_noise, x / 640.0, 0.0, z / 640.0, 4),
+            erosion: Self::get_octave_noise(&self.erosion_noise, x / 320.0, 0.0, z / 320.0, 3),
+            temperature: Self::get_octave_noise(&self.temperature_noise, x / 480.0, 0.0, z / 480.0, 2),
+            humidity: Self::get_octave_noise(&self.humidity_noise, x / 400.0, 0.0, z / 400.0, 2)
+        }
+    }
+
+    fn get_biome_kind(noise: &ColumnNoise) -> BiomeKind {
+        if noise.continentalness < OCEAN_EDGE {
+            BiomeKind::Ocean
+        } else if noise.temperature > 0.15 && noise.humidity < 0.0 {
+            BiomeKind::Desert
+        } else if noise.humidity > 0.2 {
+            BiomeKind::TemperateForest
+        } else if noise.humidity > 0.05 {
+            BiomeKind::LightForest
+        } else {
+            BiomeKind::Grassland
+        }
+    }
+
+    fn get_biome(&self, kind: BiomeKind) -> &dyn Biome {
+        match kind {
+            BiomeKind::Ocean => &self.ocean_biome,
+            BiomeKind::Grassland => &self.grass_biome,
+            BiomeKind::LightForest => &self.light_forest,
+            BiomeKind::TemperateForest => &self.temperate_forest,
+            BiomeKind::Desert => &self.desert_biome
+        }
+    }
+
+    /// @brief Height and roughness of the ground at a point, averaged over the biomes around it.
+    /// @param x World position
+    /// @param z
+    fn get_blended_shape(&self, x: f64, z: f64) -> (f64, f64) {
+        let mut height = 0.0;
+        let mut roughness = 0.0;
+        for dx in -BLEND_RADIUS..=BLEND_RADIUS {
+            for dz in -BLEND_RADIUS..=BLEND_RADIUS {
+                let noise = self.get_column_noise(
+                    x + (dx * CELL_WIDTH as i32) as f64,
+                    z + (dz * CELL_WIDTH as i32) as f64
+                );
+                let shape = Self::get_biome_kind(&noise).get_shape();
+                height += shape.height.get(noise.continentalness);
+                roughness += shape.roughness.get(noise.erosion);
+            }
+        }
+
+        let samples = ((BLEND_RADIUS * 2 + 1) * (BLEND_RADIUS * 2 + 1)) as f64;
+        (height / samples, roughness / samples)
+    }
+
+    fn get_grid_index(x: usize, y: usize, z: usize) -> usize {
+        (x * GRID_WIDTH + z) * GRID_HEIGHT + y
+    }
+
+    fn make_density_grid(&mut self, location: Vector2i) {
+        for grid_x in 0..GRID_WIDTH {
+            for grid_z in 0..GRID_WIDTH {
+                let x = (location.x * CHUNK_SIZE as i32 + (grid_x * CELL_WIDTH) as i32) as f64;
+                let z = (location.y * CHUNK_SIZE as i32 + (grid_z * CELL_WIDTH) as i32) as f64;
+                let (height, roughness) = self.get_blended_shape(x, z);
+
+                for grid_y in 0..GRID_HEIGHT {
+                    let y = (grid_y * CELL_HEIGHT) as f64;
+                    // Squashed vertically, so that the noise makes ledges more than spires
+                    let noise = Self::get_octave_noise(&self.density_noise, x / 48.0, y / 24.0, z / 48.0, 2);
+                    self.density_grid[Self::get_grid_index(grid_x, grid_y, grid_z)] =
+                        (height - y) / roughness + noise * DENSITY_NOISE_SCALE;
+                }
+            }
+        }
+    }
+
+    fn make_biome_map(&mut self, location: Vector2i) {
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let noise = self.get_column_noise(
+                    (location.x * CHUNK_SIZE as i32 + x as i32) as f64,
+                    (location.y * CHUNK_SIZE as i32 + z as i32) as f64
+                );
+                *self.biome_map.get_mut(x, z) = Self::get_biome_kind(&noise);
+            }
+        }
+    }
+
+    /// @brief Density of a block, interpolated between the corners of its cell.
+    /// @param x Position in the chunk
+    /// @param y
+    /// @param z
+    fn get_density(&self, x: usize, y: usize, z: usize) -> f64 {
+        if y < TERRAIN_FLOOR {
+            return 1.0;
+        }
+
+        let (cell_x, cell_y, cell_z) = (x / CELL_WIDTH, y / CELL_HEIGHT, z / CELL_WIDTH);
+        let tx = (x % CELL_WIDTH) as f64 / CELL_WIDTH as f64;
+        let ty = (y % CELL_HEIGHT) as f64 / CELL_HEIGHT as f64;
+        let tz = (z % CELL_WIDTH) as f64 / CELL_WIDTH as f64;
+        let corner = |dx: usize, dy: usize, dz: usize| {
+            self.density_grid[Self::get_grid_index(cell_x + dx, cell_y + dy, cell_z + dz)]
+        };
+        let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+
+        let bottom = lerp(
+            lerp(corner(0, 0, 0), corner(1, 0, 0), tx),
+            lerp(corner(0, 0, 1), corner(1, 0, 1), tx),
+            tz
+        );
+        let top = lerp(
+            lerp(corner(0, 1, 0), corner(1, 1, 0), tx),
+            lerp(corner(0, 1, 1), corner(1, 1, 1), tx),
+            tz
+        );
+        lerp(bottom, top, ty)
+    }
+
+    /// @brief Fills the chunk from the density grid, then covers every stretch of ground open
+    /// to the sky or to a cave under an overhang with a top block and dirt.
+    fn set_blocks(&mut self, p_chunk: &mut Chunk) {
+        let mut decorations = Decorations::default();
+
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let kind = *self.biome_map.get(x, z);
+                let biome = self.get_biome(kind);
+                p_chunk.set_climate(x as _, z as _, biome.get_climate());
+
+                let mut height = 0;
+                // Solid blocks since the last air or water, -1 above the highest one
+                let mut depth = -1;
+                for y in (0..TERRAIN_TOP).rev() {
+                    if self.get_density(x, y, z) <= 0.0 {
+                        if depth >= 0 {
+                            depth = 0;
+                        }
+                        // Only the sea floods, caves and hollows under the ground stay dry
+                        if depth < 0 && y <= WATER_LEVEL {
+                            p_chunk.set_block(x as _, y as _, z as _,
+                                              ChunkBlock::new_with_block_id(BlockId::Water));
+                        }
+                        continue;
+                    }
+
+                    let y = y as i32;
+                    let is_top = depth <= 0;
+                    let block = if is_top {
+                        biome.get_surface_block(&self.random, y)
+                    } else if depth <= DIRT_DEPTH {
+                        ChunkBlock::new_with_block_id(BlockId::Dirt)
+                    } else {
+                        ChunkBlock::new_with_block_id(BlockId::Stone)
+                    };
+                    p_chunk.set_block(x as _, y, z as _, block);
+
+                    if depth < 0 {
+                        height = y;
+                        decorations.add_candidates(biome, &self.random, x as _, y, z as _);
+                        depth = 0;
+                    }
+                    depth += 1;
+                }
+                *self.height_map.get_mut(x, z) = height;
+            }
+        }
+
+        self.ore_generator.place_ores(p_chunk);
+        self.cave_carver.carve(p_chunk, &self.height_map);
+
+        decorations.place(p_chunk, &self.random, |x, z| {
+            self.get_biome(*self.biome_map.get(x as _, z as _))
+        });
+    }
+}
+
+impl Default for DensityOverWorldGenerator {
+    fn default() -> Self {
+        let seed = SEED.clone();
+        Self {
+            density_grid: vec![0.0; GRID_WIDTH * GRID_WIDTH * GRID_HEIGHT],
+            height_map: Array2D::new(CHUNK_SIZE),
+            biome_map: Array2D::new(CHUNK_SIZE),
+            continentalness_noise: NoiseGenerator::new(seed.wrapping_add(104729)),
+            erosion_noise: NoiseGenerator::new(seed.wrapping_add(209459)),
+            temperature_noise: NoiseGenerator::new(seed.wrapping_add(314189)),
+            humidity_noise: NoiseGenerator::new(seed.wrapping_add(418919)),
+            density_noise: NoiseGenerator::new(seed),
+            random: Default::default(),
+            cave_carver: CaveCarver::new(seed),
+            ore_generator: OreGenerator::load(ORE_FILE, seed),
+            grass_biome: GrasslandBiome::new(seed),
+            temperate_forest: TemperateForestBiome::new(seed),
+            desert_biome: DesertBiome::new(seed),
+            ocean_biome: OceanBiome::new(seed),
+            light_forest: LightForest::new(seed)
+        }
+    }
+}
+
+impl TerrainGenerator for DensityOverWorldGenerator {
+    fn generate_terrain_for(&mut self, chunk: &mut Chunk) {
+        let location = chunk.get_location();
+        self.random.set_seed(((location.x ^ location.y) << 2) as _);
+
+        self.make_biome_map(location);
+        self.make_density_grid(location);
+        self.set_blocks(chunk);
+    }
+
+    fn generate_surface_for(&mut self, location: Vector2i) -> ChunkSurface {
+        self.random.set_seed(((location.x ^ location.y) << 2) as _);
+
+        self.make_biome_map(location);
+        self.make_density_grid(location);
+
+        // The top of the ground every column stands on. Floating islands and overhangs are left
+        // out, a heightmap would draw them as pillars reaching down to the ground.
+        let mut surface = ChunkSurface::new(location);
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let height = (TERRAIN_FLOOR..TERRAIN_TOP)
+                    .find(|&y| self.get_density(x, y, z) <= 0.0)
+                    .unwrap_or(TERRAIN_TOP) as i32 - 1;
+                let biome = self.get_biome(*self.biome_map.get(x, z));
+
+                surface.set_column(x as _, z as _, SurfaceColumn {
+                    height,
+                    block: biome.get_surface_block(&self.random, height),
+                    climate: biome.get_climate()
+                });
+            }
+        }
+        surface
+    }
+
+    fn get_minimum_spawn_height(&self) -> i32 {
+        WATER_LEVEL as _
+    }
+}
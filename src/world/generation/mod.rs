@@ -18,4 +18,8 @@ pub mod terrain_generator;
 pub mod classic_over_world_generator;
 pub mod biome;
 pub mod structure;
-pub mod super_flat_generator;
\ No newline at end of file
+pub mod super_flat_generator;
+pub mod cave_carver;
+pub mod ore_generator;
+pub mod density_over_world_generator;
+pub mod decorations;
\ No newline at end of file
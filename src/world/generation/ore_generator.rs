@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use crate::util::random::{chunk_seed, Random};
+use crate::world::block::block_id::BlockId;
+use crate::world::block::chunk_block::ChunkBlock;
+use crate::world::chunk::chunk::{Chunk, IChunk};
+use crate::world::world_constants::CHUNK_SIZE;
+
+pub const ORE_FILE: &str = "Res/Ores.txt";
+
+/// Every feature adds its index, keeping each apart from the others and from the caves.
+const ORE_SEED_SALT: i64 = 1 << 16;
+
+/// @brief Veins of one kind of block scattered through the stone, as listed in the ore file.
+#[derive(Clone, Debug)]
+pub struct OreFeature {
+    pub block: BlockId,
+    /// Blocks in a vein at most
+    pub vein_size: i32,
+    /// Veins in a chunk
+    pub count: i32,
+    pub min_height: i32,
+    pub max_height: i32
+}
+
+#[derive(Eq, PartialEq)]
+enum DecodingState {
+    Vacant,
+    Block,
+    VeinSize,
+    Count,
+    Height
+}
+
+/// @brief Places ores, and pockets of gravel or dirt, into the stone of generated chunks.
+///
+/// Every chunk draws its veins from its own seed, so the same world seed always gives
+/// the same veins whatever order chunks are generated in.
+pub struct OreGenerator {
+    features: Vec<OreFeature>,
+    seed: i32
+}
+
+impl OreFeature {
+    /// @brief Parses an ore file, each feature starting with the name of its block file after `Block`.
+    /// @param path
+    /// @return What is wrong with the file if it cannot be parsed.
+    pub fn load_all(path: &str) -> Result<Vec<Self>, String> {
+        let file = File::open(path).map_err(|_| format!("Unable to open ore file: {}!", path))?;
+        let invalid = |line: &str| format!("Invalid line in ore file {}: {}", path, line);
+
+        let mut result: Vec<Self> = Vec::new();
+        let mut state = DecodingState::Vacant;
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|_| format!("Unable to read ore file: {}!", path))?;
+            let line = line.trim();
+            if state == DecodingState::Vacant {
+                match line {
+                    "Block" => state = DecodingState::Block,
+                    "VeinSize" => state = DecodingState::VeinSize,
+                    "Count" => state = DecodingState::Count,
+                    "Height" => state = DecodingState::Height,
+                    _ => {}
+                }
+                continue;
+            }
+
+            if state == DecodingState::Block {
+                let block = BlockId::from_name(line).ok_or_else(|| invalid(line))?;
+                result.push(Self {
+                    block,
+                    vein_size: 8,
+                    count: 1,
+                    min_height: 0,
+                    max_height: 0
+                });
+                state = DecodingState::Vacant;
+                continue;
+            }
+
+            // Every other property belongs to the last block named
+            let feature = result.last_mut().ok_or_else(|| invalid(line))?;
+            match state {
+                DecodingState::VeinSize => {
+                    feature.vein_size = line.parse().map_err(|_| invalid(line))?;
+                }
+                DecodingState::Count => {
+                    feature.count = line.parse().map_err(|_| invalid(line))?;
+                }
+                DecodingState::Height => {
+                    // Lowest and highest layer: "5 64"
+                    let parts: Vec<i32> = line.split(' ')
+                        .map(|it| it.parse::<i32>())
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| invalid(line))?;
+                    if parts.len() != 2 || parts[0] > parts[1] {
+                        return Err(invalid(line));
+                    }
+                    feature.min_height = parts[0];
+                    feature.max_height = parts[1];
+                }
+                _ => {}
+            }
+            state = DecodingState::Vacant;
+        }
+
+        Ok(result)
+    }
+}
+
+impl OreGenerator {
+    /// @brief Reads the features from the ore file, placing none if it cannot be parsed.
+    /// @param path
+    /// @param seed World seed.
+    pub fn load(path: &str, seed: i32) -> Self {
+        let features = OreFeature::load_all(path).unwrap_or_else(|error| {
+            log::error!("{}", error);
+            Vec::new()
+        });
+        Self { features, seed }
+    }
+
+    /// @brief Replaces stone with the veins of every feature, in the order of the ore file.
+    /// @param chunk Filled with its terrain.
+    pub fn place_ores(&self, chunk: &mut Chunk) {
+        let location = chunk.get_location();
+        for (index, feature) in self.features.iter().enumerate() {
+            let random = Random::new_with_seed(
+                chunk_seed(self.seed, location.x, location.y, ORE_SEED_SALT + index as i64)
+            );
+            for _ in 0..feature.count {
+                let x = random.int_in_range(0..CHUNK_SIZE as i32);
+                let y = random.int_in_range(feature.min_height..=feature.max_height);
+                let z = random.int_in_range(0..CHUNK_SIZE as i32);
+                Self::place_vein(chunk, &random, feature, x, y, z);
+            }
+        }
+    }
+
+    /// @brief Wanders from block to neighbouring block, turning the stone it passes into
+    /// the feature's block. The part of a vein outside the chunk is left out.
+    fn place_vein(chunk: &mut Chunk, random: &Random, feature: &OreFeature, mut x: i32, mut y: i32, mut z: i32) {
+        let replaced = ChunkBlock::new_with_block_id(feature.block);
+        for _ in 0..feature.vein_size {
+            let is_inside = (0..CHUNK_SIZE as i32).contains(&x) && (0..CHUNK_SIZE as i32).contains(&z) && y >= 0;
+            if is_inside && chunk.get_block(x, y, z).id == BlockId::Stone as _ {
+                chunk.set_block(x, y, z, replaced);
+            }
+
+            x += random.int_in_range(-1..=1);
+            y += random.int_in_range(-1..=1);
+            z += random.int_in_range(-1..=1);
+        }
+    }
+}
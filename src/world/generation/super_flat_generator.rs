@@ -14,9 +14,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use sfml::system::Vector2i;
 use crate::world::block::block_id::BlockId;
 use crate::world::block::chunk_block::ChunkBlock;
 use crate::world::chunk::chunk::{Chunk, IChunk};
+use crate::world::chunk::chunk_surface::{ChunkSurface, SurfaceColumn};
 use crate::world::generation::terrain_generator::TerrainGenerator;
 use crate::world::world_constants::CHUNK_SIZE;
 
@@ -37,6 +39,20 @@ impl TerrainGenerator for SuperFlatGenerator {
         }
     }
 
+    fn generate_surface_for(&mut self, location: Vector2i) -> ChunkSurface {
+        let mut surface = ChunkSurface::new(location);
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                surface.set_column(x as _, z as _, SurfaceColumn {
+                    height: 4,
+                    block: ChunkBlock::new_with_block_id(BlockId::Grass),
+                    climate: Default::default()
+                });
+            }
+        }
+        surface
+    }
+
     fn get_minimum_spawn_height(&self) -> i32 {
         1
     }
@@ -14,9 +14,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use sfml::system::Vector2i;
 use crate::world::chunk::chunk::Chunk;
+use crate::world::chunk::chunk_surface::ChunkSurface;
+use crate::world::generation::classic_over_world_generator::ClassicOverWorldGenerator;
+use crate::world::generation::density_over_world_generator::DensityOverWorldGenerator;
+use crate::world::generation::super_flat_generator::SuperFlatGenerator;
 
 pub trait TerrainGenerator {
     fn generate_terrain_for(&mut self, chunk: &mut Chunk);
+    /// @brief Heights and top blocks of a chunk column, without placing any block in it.
+    /// Used for the level-of-detail meshes of distant columns.
+    fn generate_surface_for(&mut self, location: Vector2i) -> ChunkSurface;
     fn get_minimum_spawn_height(&self) -> i32;
+}
+
+/// @brief Makes the generator named by `generator` in the config file: `classic`, `density` or
+/// `flat`. Unknown names fall back to the classic generator.
+/// @param name
+pub fn new_terrain_generator(name: &str) -> Box<dyn TerrainGenerator + Send> {
+    match name {
+        "classic" => Box::new(ClassicOverWorldGenerator::new()),
+        "density" => Box::new(DensityOverWorldGenerator::new()),
+        "flat" => Box::new(SuperFlatGenerator::default()),
+        _ => {
+            log::warn!("Unknown terrain generator: {}, using the classic one.", name);
+            Box::new(ClassicOverWorldGenerator::new())
+        }
+    }
 }
\ No newline at end of file
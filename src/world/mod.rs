@@ -17,6 +17,7 @@
 pub mod world;
 pub mod chunk;
 pub mod world_constants;
+pub mod world_time;
 pub mod block;
 pub mod event;
 pub mod generation;
\ No newline at end of file
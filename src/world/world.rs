@@ -30,17 +30,24 @@ use crate::camera::Camera;
 use crate::config::Config;
 use crate::input::toggle_key::ToggleKey;
 use crate::maths::vector2xz::VectorXZ;
+use crate::physics::aabb::AABB;
 use crate::player::player::Player;
 use crate::renderer::render_master::RenderMaster;
 use crate::util;
 use crate::util::random::RandomSingleton;
 use crate::util::unsafe_cell_wrapper::UnsafeCellWrapper;
+use crate::world::block::block_database::BlockDatabase;
 use crate::world::block::chunk_block::ChunkBlock;
 use crate::world::chunk::chunk::IChunk;
+use crate::world::chunk::chunk_lod::lod_step;
 use crate::world::chunk::chunk_manager::ChunkManager;
 use crate::world::chunk::chunk_section::ChunkSection;
+use crate::world::chunk::mesh_upload_queue::UploadBudget;
 use crate::world::event::world_event::IWorldEvent;
+use crate::world::generation::biome::biome::Climate;
+use crate::world::generation::terrain_generator::new_terrain_generator;
 use crate::world::world_constants::CHUNK_SIZE;
+use crate::world::world_time::{TICKS_PER_DAY, WORLD_TIME_FILE, WorldTime};
 
 /// @brief Massive class designed to hold multiple chunks, the player, and most game aspects.
 pub struct World {
@@ -54,14 +61,22 @@ pub struct World {
 
     main_mutex: Mutex<()>,
 
-    load_distance: i32,
     render_distance: i32,
+    lod_distance: i32,
+    upload_budget: UploadBudget,
 
-    player_spawn_point: glm::TVec3<f32>
+    player_spawn_point: glm::TVec3<f32>,
+
+    time: WorldTime,
+    time_save_timer: f32,
+    skip_time_key: ToggleKey
 }
 
 const CHUNK_LOAD_THREADS_COUNT: usize = 1;
 
+/// Seconds between two saves of the world time.
+const TIME_SAVE_INTERVAL: f32 = 30.0;
+
 impl World {
     pub fn new(
         camera: Arc<UnsafeCellWrapper<Camera>>,
@@ -75,13 +90,26 @@ impl World {
             is_running: AtomicBool::new(true),
             chunk_load_threads: Vec::new(),
             main_mutex: Mutex::new(()),
-            load_distance: 0,
             render_distance: config.render_distance,
-            player_spawn_point: Default::default()
+            lod_distance: config.lod_distance,
+            upload_budget: UploadBudget {
+                max_bytes: config.upload_budget.max(0) as usize * 1024,
+                max_time: config.upload_time
+            },
+            player_spawn_point: Default::default(),
+            time: WorldTime::load(WORLD_TIME_FILE),
+            time_save_timer: 0.0,
+            skip_time_key: ToggleKey::new(Key::T)
         };
         let result = Arc::new(UnsafeCellWrapper::new(result));
         unsafe {
-            (*result.get()).chunk_manager = Some(ChunkManager::new(Arc::clone(&result)));
+            (*result.get()).chunk_manager = Some(ChunkManager::new(
+                Arc::clone(&result),
+                new_terrain_generator(&config.terrain_generator)
+            ));
+            if let Some(time) = config.world_time {
+                (*result.get()).set_time(time);
+            }
 
             (*result.get()).set_spawn_point();
             player.position = (*result.get()).player_spawn_point;
@@ -110,6 +138,31 @@ impl World {
             .get_block(bp.x, y, bp.z)
     }
 
+    /// @brief Climate of the column at the given world position.
+    pub fn get_climate(&mut self, x: i32, z: i32) -> Climate {
+        let bp = Self::get_block_xz(x, z);
+        let chunk_position = Self::get_chunk_xz(x, z);
+
+        self.chunk_manager.as_mut().unwrap().get_chunk(chunk_position.x, chunk_position.z)
+            .get_climate(bp.x, bp.z)
+    }
+
+    /// @brief Collision boxes of the block at the given position, in world space.
+    pub fn get_collision_boxes(&mut self, x: i32, y: i32, z: i32) -> Vec<AABB> {
+        let block = self.get_block(x, y, z);
+        if block.id == 0 {
+            return Vec::new();
+        }
+
+        let data = block.get_data();
+        let data = data.read().unwrap();
+        if !data.block_data().is_collidable {
+            return Vec::new();
+        }
+
+        vec![data.block_data().collision_box.offset(&glm::vec3(x as f32, y as f32, z as f32))]
+    }
+
     pub fn set_block(&mut self, x: i32, y: i32, z: i32, block: ChunkBlock) {
         if y <= 0 {
             return;
@@ -124,14 +177,15 @@ impl World {
 
     // loads chunks
     // make chunk meshes
-    pub fn update(this: &UnsafeCell<Self>, _camera: &Camera) {
+    pub fn update(this: &UnsafeCell<Self>, _camera: &Camera, delta_time: f32) {
         let mut key = ToggleKey::new(Key::C);
 
         unsafe {
+            (*this.get()).update_time(delta_time);
+
             if key.is_key_pressed() {
                 let lock = (*this.get()).main_mutex.lock().unwrap();
                 (*this.get()).chunk_manager.as_mut().unwrap().delete_meshes();
-                (*this.get()).load_distance = 2;
                 drop(lock);
             }
 
@@ -247,9 +301,15 @@ impl World {
         let lock = self.main_mutex.lock().unwrap();
 
         renderer.draw_sky();
+        renderer.set_world_time(&self.time);
 
-        let chunk_map = self.chunk_manager.as_mut().unwrap().get_chunks_mut();
+        let chunk_manager = self.chunk_manager.as_mut().unwrap();
+        let visible_sections = chunk_manager.find_visible_sections(camera, self.render_distance);
+        chunk_manager.buffer_meshes(camera, &visible_sections, self.upload_budget);
+
+        let (chunk_map, lod_map) = chunk_manager.get_chunks_and_lods_mut();
         let mut keys_to_remove: HashSet<VectorXZ> = HashSet::new();
+        let mut detailed_keys: HashSet<VectorXZ> = HashSet::new();
         for (key, chunk) in chunk_map.iter_mut() {
             let camera_x = camera.position.x as i32;
             let camera_z = camera.position.z as i32;
@@ -264,17 +324,93 @@ impl World {
             if min_x > location.x || min_z > location.y ||
                 max_z < location.y || max_x < location.x {
                 keys_to_remove.insert(*key);
-            } else {
-                chunk.draw_chunks(renderer, camera);
+            } else if chunk.has_visible_meshes(camera) || !lod_map.contains_key(key) {
+                chunk.draw_chunks(renderer, camera, &visible_sections);
+                detailed_keys.insert(*key);
             }
+            // Otherwise the coarse mesh stands in until every visible section has been meshed
         }
         for key in keys_to_remove.iter() {
             chunk_map.remove(key);
         }
 
+        let camera_chunk = Self::get_chunk_xz(camera.position.x as _, camera.position.z as _);
+        lod_map.retain(|key, _| {
+            (key.x - camera_chunk.x).abs().max((key.z - camera_chunk.z).abs()) <= self.lod_distance
+        });
+        for (key, lod) in lod_map.iter_mut() {
+            if !detailed_keys.contains(key) && camera.get_frustum().is_box_in_frustum(lod.aabb) {
+                lod.sort_translucent_faces(&camera.position);
+                renderer.draw_lod(lod);
+            }
+        }
+
+        drop(lock);
+    }
+
+    /// @brief Generates and meshes every chunk section the camera can see right away.
+    /// The load thread only follows the player's camera, so other viewpoints would show holes.
+    /// @param camera
+    pub fn make_visible_meshes(&mut self, camera: &Camera) {
+        let lock = self.main_mutex.lock().unwrap();
+
+        let camera_x = camera.position.x as i32 / CHUNK_SIZE as i32;
+        let camera_z = camera.position.z as i32 / CHUNK_SIZE as i32;
+        for x in (camera_x - self.render_distance).max(0) ..= camera_x + self.render_distance {
+            for z in (camera_z - self.render_distance).max(0) ..= camera_z + self.render_distance {
+                // One section is meshed per call
+                while self.chunk_manager.as_mut().unwrap().make_mesh(x, z, camera) {}
+            }
+        }
+
+        for x in (camera_x - self.lod_distance).max(0) ..= camera_x + self.lod_distance {
+            for z in (camera_z - self.lod_distance).max(0) ..= camera_z + self.lod_distance {
+                let distance = (x - camera_x).abs().max((z - camera_z).abs());
+                let step = lod_step(distance, self.render_distance);
+                self.chunk_manager.as_mut().unwrap().make_lod(x, z, step);
+            }
+        }
+
+        // Uploaded all at once, as the view is drawn straight away
+        self.chunk_manager.as_mut().unwrap().buffer_meshes(camera, &HashSet::new(), UploadBudget::unlimited());
+
+        drop(lock);
+    }
+
+    /// @brief Switches the block textures to another pack and rebuilds every chunk mesh with it.
+    /// @param texture_pack
+    pub fn set_texture_pack(&mut self, texture_pack: &str) {
+        let lock = self.main_mutex.lock().unwrap();
+        BlockDatabase::set_texture_pack(texture_pack);
+        self.chunk_manager.as_mut().unwrap().delete_meshes();
+        drop(lock);
+    }
+
+    /// @brief Reloads the block files and textures, then rebuilds every chunk mesh with them.
+    pub fn reload_blocks(&mut self) {
+        let lock = self.main_mutex.lock().unwrap();
+        BlockDatabase::reload();
+        self.chunk_manager.as_mut().unwrap().delete_meshes();
         drop(lock);
     }
 
+    pub fn get_time(&self) -> &WorldTime {
+        &self.time
+    }
+
+    /// @brief Moves the world clock to the given tick of the current day.
+    /// @param day_ticks
+    pub fn set_time(&mut self, day_ticks: u64) {
+        self.time.set_day_ticks(day_ticks);
+        self.time.save(WORLD_TIME_FILE);
+    }
+
+    /// @brief Moves the world clock like set_time, without saving it.
+    /// @param day_ticks
+    pub fn set_unsaved_time(&mut self, day_ticks: u64) {
+        self.time.set_day_ticks(day_ticks);
+    }
+
     pub fn get_chunk_manager(&self) -> &ChunkManager {
         self.chunk_manager.as_ref().unwrap()
     }
@@ -297,36 +433,91 @@ impl World {
 
     fn load_chunks(&mut self, camera: &Camera) {
         while self.is_running.load(Ordering::Acquire) {
-            let mut is_mesh_made = false;
+            thread::sleep(Duration::from_millis(1));
             let camera_x = camera.position.x as i32 / CHUNK_SIZE as i32;
             let camera_z = camera.position.z as i32 / CHUNK_SIZE as i32;
 
-            'inner: for i in 0..self.load_distance {
-                thread::sleep(Duration::from_millis(1));
-                let min_x = (camera_x - i).max(0);
-                let min_z = (camera_z - i).max(0);
-                let max_x = camera_x + i;
-                let max_z = camera_z + i;
-
-                for x in min_x..max_x {
-                    for z in min_z..max_z {
-                        let lock = self.main_mutex.lock().unwrap();
-                        is_mesh_made = self.chunk_manager.as_mut().unwrap().make_mesh(x, z, &camera);
-                        drop(lock);
-                    }
-                }
+            for (x, z) in self.load_order(camera, camera_x, camera_z) {
+                let lock = self.main_mutex.lock().unwrap();
+                let is_mesh_made = self.chunk_manager.as_mut().unwrap().make_mesh(x, z, &camera);
+                drop(lock);
 
+                // Start over from the top, the camera may have moved meanwhile
                 if is_mesh_made {
-                    break 'inner;
+                    break;
                 }
             }
 
-            if !is_mesh_made {
-                self.load_distance += 1;
+            // Coarse meshes are made alongside, so that distant columns fill in meanwhile
+            self.load_lod(camera_x, camera_z);
+        }
+    }
+
+    /// @brief Columns within the render distance, those in view first and then the closest ones.
+    /// Their neighbours are loaded along with them, so the outermost ring is left out.
+    /// @param camera
+    /// @param camera_x Chunk the camera is in.
+    /// @param camera_z
+    fn load_order(&self, camera: &Camera, camera_x: i32, camera_z: i32) -> Vec<(i32, i32)> {
+        let distance = self.render_distance - 1;
+        let mut columns: Vec<(bool, i32, (i32, i32))> = Vec::new();
+        for x in (camera_x - distance).max(0) ..= camera_x + distance {
+            for z in (camera_z - distance).max(0) ..= camera_z + distance {
+                // Sixteen sections high, as a column's height is not known before it is loaded
+                let column = AABB::new_with_position(
+                    &glm::vec3((x * CHUNK_SIZE as i32) as _, 0., (z * CHUNK_SIZE as i32) as _),
+                    &glm::vec3(CHUNK_SIZE as _, (CHUNK_SIZE * CHUNK_SIZE) as _, CHUNK_SIZE as _)
+                );
+                let is_out_of_view = !camera.get_frustum().is_box_in_frustum(column);
+                let ring = (x - camera_x).abs().max((z - camera_z).abs());
+                columns.push((is_out_of_view, ring, (x, z)));
             }
-            if self.load_distance >= self.render_distance {
-                self.load_distance = 2;
+        }
+
+        columns.sort_unstable();
+        columns.into_iter().map(|(_, _, column)| column).collect()
+    }
+
+    /// @brief Builds the closest level-of-detail mesh that is missing or has the wrong cell
+    /// size for its distance to the camera.
+    /// @param camera_x Chunk the camera is in.
+    /// @param camera_z
+    /// @return Whether a mesh was built.
+    fn load_lod(&mut self, camera_x: i32, camera_z: i32) -> bool {
+        for i in 0..=self.lod_distance {
+            let step = lod_step(i, self.render_distance);
+
+            let lock = self.main_mutex.lock().unwrap();
+            for x in (camera_x - i).max(0) ..= camera_x + i {
+                for z in (camera_z - i).max(0) ..= camera_z + i {
+                    // The columns inside this ring were checked already
+                    if (x - camera_x).abs() != i && (z - camera_z).abs() != i {
+                        continue;
+                    }
+
+                    if self.chunk_manager.as_mut().unwrap().make_lod(x, z, step) {
+                        drop(lock);
+                        return true;
+                    }
+                }
             }
+            drop(lock);
+        }
+        false
+    }
+
+    fn update_time(&mut self, delta_time: f32) {
+        self.time.update(delta_time);
+
+        // Skip ahead a quarter of a day
+        if self.skip_time_key.is_key_pressed() {
+            self.time.add_ticks(TICKS_PER_DAY / 4);
+        }
+
+        self.time_save_timer += delta_time;
+        if self.time_save_timer >= TIME_SAVE_INTERVAL {
+            self.time_save_timer = 0.0;
+            self.time.save(WORLD_TIME_FILE);
         }
     }
 
@@ -392,6 +583,7 @@ impl World {
 
 impl Drop for World {
     fn drop(&mut self) {
+        self.time.save(WORLD_TIME_FILE);
         *self.is_running.get_mut() = false;
         while let Some(thread) = self.chunk_load_threads.pop() {
             thread.join().unwrap();
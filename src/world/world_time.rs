@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Copyright 2024 src_resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate nalgebra_glm as glm;
+
+use std::f32::consts::PI;
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+pub const TICKS_PER_SECOND: f32 = 20.0;
+/// A full day lasts 20 minutes of real time.
+pub const TICKS_PER_DAY: u64 = 24000;
+
+/// Tick at which a freshly created world starts: shortly after sunrise.
+pub const DEFAULT_START_TICK: u64 = 1000;
+
+/// Light level terrain keeps at midnight, so the world never goes pitch black.
+pub const MIN_DAYLIGHT: f32 = 0.2;
+
+pub const WORLD_TIME_FILE: &str = "world_time.txt";
+
+const DAY_ZENITH_COLOUR: [f32; 3] = [0.32, 0.55, 0.95];
+const DAY_HORIZON_COLOUR: [f32; 3] = [0.7, 0.83, 1.0];
+const NIGHT_ZENITH_COLOUR: [f32; 3] = [0.01, 0.01, 0.04];
+const NIGHT_HORIZON_COLOUR: [f32; 3] = [0.04, 0.05, 0.1];
+const SUNSET_COLOUR: [f32; 3] = [1.0, 0.45, 0.15];
+
+/// @brief Time of day of a world, counted in ticks.
+///
+/// Tick 0 is sunrise, `TICKS_PER_DAY / 4` is noon, `TICKS_PER_DAY / 2` is sunset
+/// and `TICKS_PER_DAY * 3 / 4` is midnight.
+#[derive(Copy, Clone, Debug)]
+pub struct WorldTime {
+    ticks: u64,
+    partial_tick: f32
+}
+
+impl WorldTime {
+    pub fn new(ticks: u64) -> Self {
+        Self {
+            ticks,
+            partial_tick: 0.0
+        }
+    }
+
+    /// @brief Reads the world time saved by `save`, falling back to a new morning.
+    /// @param file_path
+    pub fn load(file_path: &str) -> Self {
+        let Ok(contents) = fs::read_to_string(file_path) else {
+            return Self::default();
+        };
+
+        for line in contents.lines() {
+            let parts: Vec<_> = line.trim().split(' ').collect();
+            if parts.len() > 1 && parts[0] == "ticks" {
+                if let Ok(ticks) = parts[1].parse() {
+                    log::debug!("World time: {} ticks", ticks);
+                    return Self::new(ticks);
+                }
+            }
+        }
+
+        log::warn!("Unable to read world time from {}, starting a new day.", file_path);
+        Self::default()
+    }
+
+    pub fn save(&self, file_path: &str) {
+        let result = File::create(file_path).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            writeln!(writer, "ticks {}", self.ticks)?;
+            writer.flush()
+        });
+
+        if let Err(e) = result {
+            log::error!("Unable to save world time to {}: {}", file_path, e);
+        }
+    }
+
+    /// @brief Advances the time by the ticks that fit in `dt` seconds.
+    /// @param dt
+    /// @return Number of whole ticks that passed.
+    pub fn update(&mut self, dt: f32) -> u64 {
+        self.partial_tick += dt * TICKS_PER_SECOND;
+        let passed = self.partial_tick.floor();
+        self.partial_tick -= passed;
+        self.ticks += passed as u64;
+        passed as u64
+    }
+
+    /// @brief Total ticks since the world was created.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn day(&self) -> u64 {
+        self.ticks / TICKS_PER_DAY
+    }
+
+    pub fn day_ticks(&self) -> u64 {
+        self.ticks % TICKS_PER_DAY
+    }
+
+    /// @brief Moves the clock to the given tick of the current day.
+    /// @param day_ticks
+    pub fn set_day_ticks(&mut self, day_ticks: u64) {
+        self.ticks = self.day() * TICKS_PER_DAY + day_ticks % TICKS_PER_DAY;
+        self.partial_tick = 0.0;
+    }
+
+    pub fn add_ticks(&mut self, ticks: u64) {
+        self.ticks += ticks;
+    }
+
+    /// @brief Progress through the current day, in the range [0, 1).
+    pub fn time_of_day(&self) -> f32 {
+        (self.day_ticks() as f32 + self.partial_tick) / TICKS_PER_DAY as f32
+    }
+
+    /// @brief Angle of the sun around the world, 0 at sunrise.
+    pub fn celestial_angle(&self) -> f32 {
+        self.time_of_day() * 2.0 * PI
+    }
+
+    /// @brief Unit vector pointing towards the sun. The moon is always opposite.
+    pub fn sun_direction(&self) -> glm::TVec3<f32> {
+        let angle = self.celestial_angle();
+        glm::normalize(&glm::vec3(angle.cos(), angle.sin(), 0.25))
+    }
+
+    /// @brief How much the sun is up, 0 at night and 1 during the day.
+    pub fn sun_visibility(&self) -> f32 {
+        smooth_step(-0.15, 0.2, self.sun_direction().y)
+    }
+
+    /// @brief Light multiplier applied to the terrain, in the range [MIN_DAYLIGHT, 1].
+    pub fn daylight(&self) -> f32 {
+        MIN_DAYLIGHT + (1.0 - MIN_DAYLIGHT) * self.sun_visibility()
+    }
+
+    pub fn star_visibility(&self) -> f32 {
+        1.0 - smooth_step(-0.3, 0.05, self.sun_direction().y)
+    }
+
+    /// @brief Strength of the sunrise/sunset glow, peaking while the sun crosses the horizon.
+    pub fn sunset_strength(&self) -> f32 {
+        1.0 - smooth_step(0.0, 0.35, self.sun_direction().y.abs())
+    }
+
+    pub fn zenith_colour(&self) -> glm::TVec3<f32> {
+        glm::lerp(
+            &glm::make_vec3(&NIGHT_ZENITH_COLOUR),
+            &glm::make_vec3(&DAY_ZENITH_COLOUR),
+            self.sun_visibility()
+        )
+    }
+
+    pub fn horizon_colour(&self) -> glm::TVec3<f32> {
+        let horizon = glm::lerp(
+            &glm::make_vec3(&NIGHT_HORIZON_COLOUR),
+            &glm::make_vec3(&DAY_HORIZON_COLOUR),
+            self.sun_visibility()
+        );
+        glm::lerp(&horizon, &glm::make_vec3(&SUNSET_COLOUR), self.sunset_strength() * 0.5)
+    }
+
+    pub fn sunset_colour(&self) -> glm::TVec3<f32> {
+        glm::make_vec3(&SUNSET_COLOUR) * self.sunset_strength()
+    }
+}
+
+impl Default for WorldTime {
+    fn default() -> Self {
+        Self::new(DEFAULT_START_TICK)
+    }
+}
+
+fn smooth_step(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}